@@ -15,10 +15,21 @@
     along with this program. If not, see <http://www.gnu.org/licenses/>.
 */
 
-use constants::LABEL;
-use errors::{ZiaError, ZiaResult};
-use reading::{FindDefinition, MaybeString, MightExpand};
+use building::{ContextBuilder, Feature};
+use computing::{parse_integer, Integers};
+use constants::{BUILTIN_COUNT, LABEL, RESERVED_BUILTIN_COUNT};
+use diagnosing::{SetWarnings, Warn};
+use errors::{ZiaError, ZiaResult, ZiaWarning};
+use limiting::SetLimits;
+use localising::{Locale, SetLocale};
+use normalising::SetNormalisation;
+use reading::{
+    is_variable_symbol, FindDefinition, FindWhatReducesToIt, MaybeString, MightExpand, Patterns,
+};
+use renaming::LabelHistory;
 use std::{fmt, rc::Rc};
+use suggesting::SetStrictness;
+use translating::SyntaxFinder;
 use writing::{
     DeleteReduction, GetDefinition, GetDefinitionOf, GetNormalForm, GetReduction, InsertDefinition,
     MakeReduceFrom, MaybeConcept, NoLongerReducesFrom, RemoveReduction, SetAsDefinitionOf,
@@ -27,7 +38,7 @@ use writing::{
 
 pub trait ExecuteReduction<T>
 where
-    Self: ConceptMaker<T> + DeleteReduction<T>,
+    Self: ConceptMaker<T> + DeleteReduction<T> + Patterns<T>,
     T: SetReduction
         + From<Self::C>
         + From<Self::A>
@@ -40,27 +51,38 @@ where
         + SetDefinition
         + SetAsDefinitionOf
         + GetDefinition
-        + MaybeString,
+        + MaybeString
+        + FindWhatReducesToIt
+        + From<i64>,
     Self::S: Container + PartialEq,
 {
-    fn execute_reduction(&mut self, syntax: &Self::S, normal_form: &Self::S) -> ZiaResult<String> {
+    fn execute_reduction(&mut self, syntax: &Self::S, normal_form: &Self::S) -> ZiaResult<()> {
         if normal_form.contains(syntax) {
-            Err(ZiaError::ExpandingReduction)
+            Err(ZiaError::ExpandingReduction {
+                concept: syntax.to_string(),
+                reduction: normal_form.to_string(),
+            })
         } else if syntax == normal_form {
-            try!(self.try_removing_reduction::<Self::S>(syntax));
-            Ok("".to_string())
+            self.try_removing_reduction::<Self::S>(syntax)
         } else {
             let syntax_concept = try!(self.concept_from_ast(syntax));
             let normal_form_concept = try!(self.concept_from_ast(normal_form));
+            try!(self.check_reduction_rule(syntax_concept, normal_form_concept));
             try!(self.update_reduction(syntax_concept, normal_form_concept));
-            Ok("".to_string())
+            for (rule, shadowing_rule) in self.find_unreachable_rules(syntax_concept) {
+                self.warn(ZiaWarning::UnreachableRule {
+                    rule: self.display(rule),
+                    shadowing_rule: self.display(shadowing_rule),
+                });
+            }
+            Ok(())
         }
     }
 }
 
 impl<S, T> ExecuteReduction<T> for S
 where
-    S: ConceptMaker<T> + DeleteReduction<T>,
+    S: ConceptMaker<T> + DeleteReduction<T> + Patterns<T>,
     T: SetReduction
         + MakeReduceFrom
         + GetDefinitionOf
@@ -73,7 +95,9 @@ where
         + SetDefinition
         + SetAsDefinitionOf
         + GetDefinition
-        + MaybeString,
+        + MaybeString
+        + FindWhatReducesToIt
+        + From<i64>,
     Self::S: Container + PartialEq<Self::S>,
 {
 }
@@ -105,23 +129,45 @@ where
         + SetDefinition
         + SetAsDefinitionOf
         + MaybeString
-        + GetReduction,
-    Self: Labeller<T> + GetNormalForm<T>,
+        + GetReduction
+        + FindWhatReducesToIt
+        + From<i64>,
+    Self: Labeller<T> + GetNormalForm<T> + SyntaxFinder<T> + IntegerMaker<T> + Integers + Warn,
 {
     type S: MightExpand<Self::S> + MaybeConcept + fmt::Display;
+    /// Returns the concept of the syntax, adding concepts for new symbols and compositions. New symbols are labelled within the current module, except for integer literals, which are new integer concepts if integers are read. A symbol may have been labelled since the syntax was parsed, e.g. if it also occurs earlier in the same command.
     fn concept_from_ast(&mut self, ast: &Self::S) -> ZiaResult<usize> {
         if let Some(c) = ast.get_concept() {
             Ok(c)
         } else {
             let string = &ast.to_string();
             match ast.get_expansion() {
-                None => self.new_labelled_default(string),
+                None => match self.concept_from_label(string) {
+                    Some(c) => Ok(c),
+                    None => match parse_integer(string).filter(|_| self.reads_integers()) {
+                        Some(n) => {
+                            let integer = self.new_integer(n);
+                            try!(self.label(integer, string));
+                            Ok(integer)
+                        }
+                        None => {
+                            if !is_variable_symbol(string) {
+                                self.warn(ZiaWarning::ImplicitSymbol {
+                                    symbol: string.clone(),
+                                });
+                            }
+                            let label = self.qualify(string);
+                            self.new_labelled_default(&label)
+                        }
+                    },
+                },
                 Some((ref left, ref right)) => {
                     let mut leftc = try!(self.concept_from_ast(left));
                     let mut rightc = try!(self.concept_from_ast(right));
                     let concept = try!(self.find_or_insert_definition(leftc, rightc));
                     if !string.contains(' ') {
-                        try!(self.label(concept, string));
+                        let label = self.qualify(string);
+                        try!(self.label(concept, &label));
                     }
                     Ok(concept)
                 }
@@ -133,7 +179,15 @@ where
 /// Preparing a context by labelling concrete concepts.
 pub trait ContextMaker<T>
 where
-    Self: Labeller<T> + Default,
+    Self: Labeller<T>
+        + SetLocale
+        + SetNormalisation
+        + SetLimits
+        + LabelHistory
+        + Integers
+        + SetWarnings
+        + SetStrictness
+        + Default,
     T: GetDefinitionOf
         + From<String>
         + From<Self::C>
@@ -146,16 +200,50 @@ where
         + SetAsDefinitionOf
         + MaybeString,
 {
+    /// Constructs a context whose built-in concepts are labelled in English.
     fn new() -> Self {
+        Self::with_locale(Locale::default())
+    }
+    /// Constructs a context whose built-in concepts are labelled, and whose error messages are written, in the language of `locale`.
+    fn with_locale(locale: Locale) -> Self {
+        Self::from_builder(&ContextBuilder::new().locale(locale)).unwrap()
+    }
+    /// Constructs a context configured by `builder`. Returns an error if the chosen labels are not valid symbols or the same label is chosen twice.
+    fn from_builder(builder: &ContextBuilder) -> ZiaResult<Self> {
+        let labels = try!(builder.concrete_labels());
         let mut cont = Self::default();
-        cont.setup().unwrap();
-        cont
+        cont.set_locale(builder.get_locale());
+        try!(cont.set_normalisation(builder.get_normalisation()));
+        try!(cont.setup(&labels));
+        cont.set_step_limit(builder.get_step_limit());
+        cont.set_cancellation_flag(builder.get_cancellation_flag());
+        cont.set_resolving_previous_labels(builder.has_feature(Feature::LabelHistory));
+        cont.set_reading_integers(
+            builder.has_feature(Feature::Arithmetic) || builder.has_feature(Feature::Booleans),
+        );
+        cont.set_strictness(builder.get_strictness());
+        for &(category, allowed) in builder.get_warning_settings() {
+            if allowed {
+                cont.allow_warnings(category);
+            } else {
+                cont.suppress_warnings(category);
+            }
+        }
+        Ok(cont)
     }
 }
 
 impl<S, T> ContextMaker<T> for S
 where
-    S: Labeller<T> + Default,
+    S: Labeller<T>
+        + SetLocale
+        + SetNormalisation
+        + SetLimits
+        + LabelHistory
+        + Integers
+        + SetWarnings
+        + SetStrictness
+        + Default,
     T: GetDefinitionOf
         + From<String>
         + From<Self::C>
@@ -197,15 +285,23 @@ where
         try!(self.label(new_default, string));
         Ok(new_default)
     }
-    fn setup(&mut self) -> ZiaResult<()> {
-        let label_concept = self.new_default::<Self::C>();
-        let define_concept = self.new_default::<Self::C>();
-        let reduction_concept = self.new_default::<Self::C>();
-        let let_concept = self.new_default::<Self::C>();
-		try!(self.label(label_concept, "label_of"));
-        try!(self.label(define_concept, ":="));
-        try!(self.label(reduction_concept, "->"));
-        self.label(let_concept, "let")
+    /// Adds a concrete concept for each of `labels` in order of index, except for the indices reserved for built-in concepts of later versions, which are left without concepts. Then labels those that have a label.
+    fn setup(&mut self, labels: &[Option<String>]) -> ZiaResult<()> {
+        let mut concepts = Vec::new();
+        for (index, label) in labels.iter().enumerate() {
+            if index == BUILTIN_COUNT {
+                self.reserve_indices(RESERVED_BUILTIN_COUNT);
+            }
+            if !(BUILTIN_COUNT..RESERVED_BUILTIN_COUNT).contains(&index) {
+                concepts.push((self.new_default::<Self::C>(), label));
+            }
+        }
+        for (concept, label) in concepts {
+            if let Some(ref l) = *label {
+                try!(self.label(concept, l));
+            }
+        }
+        Ok(())
     }
 }
 
@@ -216,7 +312,8 @@ where
         + GetReduction
         + SetDefinition
         + SetAsDefinitionOf
-        + GetDefinitionOf,
+        + GetDefinitionOf
+        + MaybeString,
     Self: DefaultMaker<T> + InsertDefinition<T> + FindDefinition<T>,
 {
     type A: Default;
@@ -253,6 +350,23 @@ where
 {
 }
 
+pub trait IntegerMaker<T>
+where
+    T: From<i64>,
+    Self: ConceptAdder<T>,
+{
+    fn new_integer(&mut self, integer: i64) -> usize {
+        self.add_concept(integer.into())
+    }
+}
+
+impl<S, T> IntegerMaker<T> for S
+where
+    T: From<i64>,
+    S: ConceptAdder<T>,
+{
+}
+
 pub trait DefaultMaker<T>
 where
     Self: ConceptAdder<T>,
@@ -271,4 +385,6 @@ pub trait StringAdder {
 
 pub trait ConceptAdder<T> {
     fn add_concept(&mut self, T) -> usize;
+    /// Leaves each index below `end` that hasn't been used without a concept. New concepts are never given these indices.
+    fn reserve_indices(&mut self, end: usize);
 }
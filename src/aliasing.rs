@@ -0,0 +1,107 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+use adding::Labeller;
+use errors::{ZiaError, ZiaResult};
+use reading::{
+    FindWhatReducesToIt, GetDefinition, GetDefinitionOf, GetLabel, GetReduction, MaybeString,
+};
+use writing::{
+    MakeReduceFrom, NoLongerReducesFrom, RemoveReduction, SetAsDefinitionOf, SetDefinition,
+    SetReduction, Unlabeller,
+};
+
+/// Storing the aliases of concepts, i.e. symbols that refer to a concept besides the label used to display it.
+pub trait Aliases {
+    fn add_alias(&mut self, alias: &str, concept: usize);
+    /// Removes the alias and returns the concept it referred to.
+    fn remove_alias(&mut self, alias: &str) -> Option<usize>;
+    fn get_aliased(&self, alias: &str) -> Option<usize>;
+    /// Returns the aliases of the concept in alphabetical order.
+    fn get_aliases(&self, concept: usize) -> Vec<String>;
+    /// Returns each alias, as it was written, whose normalised text starts with `prefix`.
+    fn aliases_starting_with(&self, prefix: &str) -> Vec<String>;
+}
+
+/// Removing aliases and swapping an alias with the label of its concept.
+pub trait Aliaser<T>
+where
+    T: SetReduction
+        + MakeReduceFrom
+        + From<String>
+        + GetDefinitionOf
+        + SetDefinition
+        + SetAsDefinitionOf
+        + GetReduction
+        + GetDefinition
+        + MaybeString
+        + RemoveReduction
+        + NoLongerReducesFrom
+        + FindWhatReducesToIt
+        + From<Self::C>
+        + From<Self::A>,
+    Self: Aliases + Labeller<T> + Unlabeller<T> + GetLabel<T>,
+{
+    /// Removes the alias, or returns `Err(ZiaError::UnknownAlias)` if the symbol isn't an alias.
+    fn unalias(&mut self, alias: &str) -> ZiaResult<()> {
+        match self.remove_alias(alias) {
+            Some(_) => Ok(()),
+            None => Err(ZiaError::UnknownAlias {
+                symbol: alias.to_string(),
+            }),
+        }
+    }
+    /// Makes the alias the label of its concept. The previous label of the concept becomes one of its aliases.
+    fn prefer(&mut self, alias: &str) -> ZiaResult<()> {
+        let concept = match self.remove_alias(alias) {
+            Some(c) => c,
+            None => {
+                return Err(ZiaError::UnknownAlias {
+                    symbol: alias.to_string(),
+                })
+            }
+        };
+        match self.get_label(concept) {
+            Some(label) => {
+                try!(self.unlabel(concept));
+                try!(self.label(concept, alias));
+                self.add_alias(&label, concept);
+                Ok(())
+            }
+            None => self.label(concept, alias),
+        }
+    }
+}
+
+impl<S, T> Aliaser<T> for S
+where
+    T: SetReduction
+        + MakeReduceFrom
+        + From<String>
+        + GetDefinitionOf
+        + SetDefinition
+        + SetAsDefinitionOf
+        + GetReduction
+        + GetDefinition
+        + MaybeString
+        + RemoveReduction
+        + NoLongerReducesFrom
+        + FindWhatReducesToIt
+        + From<Self::C>
+        + From<Self::A>,
+    S: Aliases + Labeller<T> + Unlabeller<T> + GetLabel<T>,
+{
+}
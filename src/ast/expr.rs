@@ -0,0 +1,69 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+use std::fmt;
+
+/// Syntax built without parsing a string. It is linked to concepts when it is executed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    /// A leaf of the syntax tree.
+    Symbol(String),
+    /// A lefthand and righthand expression grouped together.
+    Pair(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Constructs a leaf of the syntax tree from the text of a symbol.
+    pub fn symbol(symbol: &str) -> Expr {
+        Expr::Symbol(symbol.to_string())
+    }
+    /// Groups `self` as the lefthand expression with `righthand`.
+    pub fn pair(self, righthand: Expr) -> Expr {
+        Expr::Pair(Box::new(self), Box::new(righthand))
+    }
+    /// Gets the text of the symbol if the expression is a leaf.
+    pub fn get_symbol(&self) -> Option<&str> {
+        match *self {
+            Expr::Symbol(ref s) => Some(s),
+            Expr::Pair(_, _) => None,
+        }
+    }
+    /// An expression is encapsulated in parentheses when joined with other syntax whereas a symbol is not.
+    fn display_joint(&self) -> String {
+        match *self {
+            Expr::Symbol(ref s) => s.clone(),
+            Expr::Pair(_, _) => "(".to_string() + &self.to_string() + ")",
+        }
+    }
+}
+
+impl From<&str> for Expr {
+    fn from(symbol: &str) -> Expr {
+        Expr::symbol(symbol)
+    }
+}
+
+impl fmt::Display for Expr {
+    /// Displays the expression in the syntax that would be parsed into the same expression.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Expr::Symbol(ref s) => write!(f, "{}", s),
+            Expr::Pair(ref left, ref right) => {
+                write!(f, "{} {}", left.display_joint(), right.display_joint())
+            }
+        }
+    }
+}
@@ -14,6 +14,9 @@
     You should have received a copy of the GNU General Public License
     along with this program. If not, see <http://www.gnu.org/licenses/>.
 */
+mod expr;
+
+pub use self::expr::Expr;
 use reading::{DisplayJoint, MaybeConcept, MightExpand, Pair};
 use std::{fmt, rc::Rc};
 
@@ -0,0 +1,317 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+use constants::{
+    ADD, ALIAS, CASCADING_DELETE, DEFINE, DELETE, EQUALS, EVENTUALLY_REDUCES_FROM, FALSE,
+    FORGET_LABELS, GREATER, LABEL, LESS, LET, MODULE, MULTIPLY, NORMAL_FORM, OPEN, PREFER,
+    PREVIOUS_LABELS, REDUCES_FROM, REDUCTION, RESERVED_BUILTIN_COUNT, SEARCH, SUBTRACT, TRACE,
+    TRANSITIVELY_USED_IN, TRUE, UNALIAS, USED_IN,
+};
+use errors::{WarningCategory, ZiaError, ZiaResult};
+use localising::Locale;
+use normalising::Normalisation;
+use std::{
+    collections::HashMap,
+    sync::{atomic::AtomicBool, Arc},
+};
+use suggesting::Strictness;
+use translating::is_valid_symbol;
+
+/// A concept built into every context whose label can be chosen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Builtin {
+    /// Labelled `label_of` in English.
+    Label,
+    /// Labelled `:=` in English.
+    Define,
+    /// Labelled `->` in English.
+    Reduction,
+    /// Labelled `let` in English.
+    Let,
+    /// Labelled `->*` in English.
+    NormalForm,
+    /// Labelled `delete` in English. Part of `Feature::Deletion`.
+    Delete,
+    /// Labelled `delete*` in English. Part of `Feature::Deletion`.
+    CascadingDelete,
+    /// Labelled `alias_of` in English. Part of `Feature::Aliases`.
+    Alias,
+    /// Labelled `unalias` in English. Part of `Feature::Aliases`.
+    Unalias,
+    /// Labelled `prefer` in English. Part of `Feature::Aliases`.
+    Prefer,
+    /// Labelled `module` in English. Part of `Feature::Modules`.
+    Module,
+    /// Labelled `open` in English. Part of `Feature::Modules`.
+    Open,
+    /// Labelled `previous_labels` in English. Part of `Feature::LabelHistory`.
+    PreviousLabels,
+    /// Labelled `forget_labels` in English. Part of `Feature::LabelHistory`.
+    ForgetLabels,
+    /// Labelled `trace` in English. Part of `Feature::Introspection`.
+    Trace,
+    /// Labelled `+` in English. Part of `Feature::Arithmetic`.
+    Add,
+    /// Labelled `-` in English. Part of `Feature::Arithmetic`.
+    Subtract,
+    /// Labelled `*` in English. Part of `Feature::Arithmetic`.
+    Multiply,
+    /// Labelled `<` in English. Part of `Feature::Arithmetic`.
+    Less,
+    /// Labelled `>` in English. Part of `Feature::Arithmetic`.
+    Greater,
+    /// Labelled `true` in English. Part of `Feature::Arithmetic` and `Feature::Booleans`.
+    True,
+    /// Labelled `false` in English. Part of `Feature::Arithmetic` and `Feature::Booleans`.
+    False,
+    /// Labelled `=` in English. Part of `Feature::Booleans`.
+    Equals,
+    /// Labelled `<-` in English. Part of `Feature::Introspection`.
+    ReducesFrom,
+    /// Labelled `<-*` in English. Part of `Feature::Introspection`.
+    EventuallyReducesFrom,
+    /// Labelled `used_in` in English. Part of `Feature::Introspection`.
+    UsedIn,
+    /// Labelled `used_in*` in English. Part of `Feature::Introspection`.
+    TransitivelyUsedIn,
+    /// Labelled `search` in English. Part of `Feature::Introspection`.
+    Search,
+}
+
+impl Builtin {
+    /// The index of the concept.
+    fn id(self) -> usize {
+        match self {
+            Builtin::Label => LABEL,
+            Builtin::Define => DEFINE,
+            Builtin::Reduction => REDUCTION,
+            Builtin::Let => LET,
+            Builtin::NormalForm => NORMAL_FORM,
+            Builtin::Delete => DELETE,
+            Builtin::CascadingDelete => CASCADING_DELETE,
+            Builtin::Alias => ALIAS,
+            Builtin::Unalias => UNALIAS,
+            Builtin::Prefer => PREFER,
+            Builtin::Module => MODULE,
+            Builtin::Open => OPEN,
+            Builtin::PreviousLabels => PREVIOUS_LABELS,
+            Builtin::ForgetLabels => FORGET_LABELS,
+            Builtin::Trace => TRACE,
+            Builtin::Add => ADD,
+            Builtin::Subtract => SUBTRACT,
+            Builtin::Multiply => MULTIPLY,
+            Builtin::Less => LESS,
+            Builtin::Greater => GREATER,
+            Builtin::True => TRUE,
+            Builtin::False => FALSE,
+            Builtin::Equals => EQUALS,
+            Builtin::ReducesFrom => REDUCES_FROM,
+            Builtin::EventuallyReducesFrom => EVENTUALLY_REDUCES_FROM,
+            Builtin::UsedIn => USED_IN,
+            Builtin::TransitivelyUsedIn => TRANSITIVELY_USED_IN,
+            Builtin::Search => SEARCH,
+        }
+    }
+}
+
+/// An optional set of built-in concepts. Concepts of a feature set that isn't enabled keep their
+/// index but are left unlabelled so that they cannot be used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Feature {
+    /// Commands for inspecting how expressions are reduced: `trace`, `<-`, which lists what
+    /// reduces to an expression in one step, `<-*`, which lists what reduces to an expression in
+    /// any number of steps, `used_in`, which lists the compositions of an expression, and
+    /// `used_in*`, which also lists the compositions of those compositions, and `search`, which
+    /// lists the labels matching a glob.
+    Introspection,
+    /// Operators on integers whose reductions are computed natively: `+`, `-`, `*`, `<` and `>`,
+    /// and the `true` and `false` results of comparisons.
+    Arithmetic,
+    /// The `true` and `false` concepts and `=`, which tests whether two expressions have the same
+    /// normal form.
+    Booleans,
+    /// Commands for organising labels into modules: `module`, which changes the module that new
+    /// labels belong to, and `open`, which lets the labels of a module be used without
+    /// qualification.
+    Modules,
+    /// Reading the previous labels of relabelled concepts, with a warning, and commands for
+    /// managing them: `previous_labels`, which lists the previous labels of a concept, and
+    /// `forget_labels`, which stops the previous labels of a concept from being read.
+    LabelHistory,
+    /// Commands for deleting concepts: `delete`, which only deletes a concept that no other
+    /// concept depends on, and `delete*`, which also deletes the concepts that depend on it.
+    Deletion,
+    /// Commands for referring to concepts with several symbols: `alias_of`, which makes a symbol
+    /// an alias of a concept, `unalias`, which removes an alias, and `prefer`, which makes an alias
+    /// the label of its concept.
+    Aliases,
+}
+
+impl Feature {
+    /// The indices of the built-in concepts of the feature set.
+    fn concepts(self) -> &'static [usize] {
+        match self {
+            Feature::Introspection => &[
+                TRACE,
+                REDUCES_FROM,
+                EVENTUALLY_REDUCES_FROM,
+                USED_IN,
+                TRANSITIVELY_USED_IN,
+                SEARCH,
+            ],
+            Feature::Arithmetic => &[ADD, SUBTRACT, MULTIPLY, LESS, GREATER, TRUE, FALSE],
+            Feature::Booleans => &[TRUE, FALSE, EQUALS],
+            Feature::Modules => &[MODULE, OPEN],
+            Feature::LabelHistory => &[PREVIOUS_LABELS, FORGET_LABELS],
+            Feature::Deletion => &[DELETE, CASCADING_DELETE],
+            Feature::Aliases => &[ALIAS, UNALIAS, PREFER],
+        }
+    }
+}
+
+/// Configuring how a context is constructed.
+#[derive(Clone, Debug, Default)]
+pub struct ContextBuilder {
+    locale: Locale,
+    normalisation: Normalisation,
+    builtin_labels: HashMap<usize, String>,
+    concrete_labels: Vec<String>,
+    features: Vec<Feature>,
+    step_limit: Option<usize>,
+    cancellation_flag: Option<Arc<AtomicBool>>,
+    warning_settings: Vec<(WarningCategory, bool)>,
+    strictness: Strictness,
+}
+
+impl ContextBuilder {
+    /// A builder for a context with the same configuration as `ContextMaker::new`.
+    pub fn new() -> ContextBuilder {
+        ContextBuilder::default()
+    }
+    /// Sets the language of error messages and of the labels of built-in concepts that aren't chosen with `builtin_label`.
+    pub fn locale(mut self, locale: Locale) -> ContextBuilder {
+        self.locale = locale;
+        self
+    }
+    /// Sets how the text of symbols is normalised.
+    pub fn normalisation(mut self, normalisation: Normalisation) -> ContextBuilder {
+        self.normalisation = normalisation;
+        self
+    }
+    /// Chooses the label of a built-in concept.
+    pub fn builtin_label(mut self, builtin: Builtin, label: &str) -> ContextBuilder {
+        self.builtin_labels.insert(builtin.id(), label.to_string());
+        self
+    }
+    /// Adds a concrete concept with a label. Its index is given by `reserved_id`.
+    pub fn concrete(mut self, label: &str) -> ContextBuilder {
+        self.concrete_labels.push(label.to_string());
+        self
+    }
+    /// Enables an optional set of built-in concepts.
+    pub fn feature(mut self, feature: Feature) -> ContextBuilder {
+        if !self.features.contains(&feature) {
+            self.features.push(feature);
+        }
+        self
+    }
+    /// Limits the number of steps taken to reduce and expand expressions for each command.
+    pub fn step_limit(mut self, limit: usize) -> ContextBuilder {
+        self.step_limit = Some(limit);
+        self
+    }
+    /// Sets a flag that aborts evaluation when set to `true`, e.g. from another thread.
+    pub fn cancellation_flag(mut self, flag: Arc<AtomicBool>) -> ContextBuilder {
+        self.cancellation_flag = Some(flag);
+        self
+    }
+    /// Stops warnings of the category from being given.
+    pub fn suppress_warnings(mut self, category: WarningCategory) -> ContextBuilder {
+        self.warning_settings.push((category, false));
+        self
+    }
+    /// Gives warnings of the category, including those suppressed by default.
+    pub fn allow_warnings(mut self, category: WarningCategory) -> ContextBuilder {
+        self.warning_settings.push((category, true));
+        self
+    }
+    /// Chooses which commands are rejected if they contain symbols that aren't used.
+    pub fn strictness(mut self, strictness: Strictness) -> ContextBuilder {
+        self.strictness = strictness;
+        self
+    }
+    /// The index that the concrete concept added with `label` will have once the context is built. Indices are given in the order concrete concepts are added, starting at 64 after a block reserved for built-in concepts, so they stay the same when built-in concepts are added in later versions.
+    pub fn reserved_id(&self, label: &str) -> Option<usize> {
+        self.concrete_labels
+            .iter()
+            .position(|l| l == label)
+            .map(|position| RESERVED_BUILTIN_COUNT + position)
+    }
+    /// Whether the feature set is enabled.
+    pub fn has_feature(&self, feature: Feature) -> bool {
+        self.features.contains(&feature)
+    }
+    pub fn get_locale(&self) -> Locale {
+        self.locale
+    }
+    pub fn get_normalisation(&self) -> Normalisation {
+        self.normalisation
+    }
+    pub fn get_step_limit(&self) -> Option<usize> {
+        self.step_limit
+    }
+    /// Returns whether each category of warnings is allowed, in the order they were chosen.
+    pub fn get_warning_settings(&self) -> &[(WarningCategory, bool)] {
+        &self.warning_settings
+    }
+    pub fn get_strictness(&self) -> Strictness {
+        self.strictness
+    }
+    pub fn get_cancellation_flag(&self) -> Option<Arc<AtomicBool>> {
+        self.cancellation_flag.clone()
+    }
+    /// Returns the label of each concrete concept in order of index. Built-in concepts of feature sets that aren't enabled are unlabelled.
+    pub fn concrete_labels(&self) -> ZiaResult<Vec<Option<String>>> {
+        let mut labels: Vec<Option<String>> = vec![None; RESERVED_BUILTIN_COUNT];
+        let mut enabled = vec![LABEL, DEFINE, REDUCTION, LET, NORMAL_FORM];
+        for feature in &self.features {
+            enabled.extend_from_slice(feature.concepts());
+        }
+        for concept in enabled {
+            labels[concept] = match self.builtin_labels.get(&concept) {
+                Some(l) => Some(l.clone()),
+                None => self.locale.builtin_label(concept).map(|l| l.to_string()),
+            };
+        }
+        for label in &self.concrete_labels {
+            labels.push(Some(label.clone()));
+        }
+        let mut normalised_labels: HashMap<String, &str> = HashMap::new();
+        for label in labels.iter().filter_map(|l| l.as_ref()) {
+            if !is_valid_symbol(label) {
+                return Err(ZiaError::InvalidSymbol);
+            }
+            let normalised_label = self.normalisation.normalise(label);
+            if let Some(other) = normalised_labels.insert(normalised_label, label) {
+                return Err(ZiaError::DefinitionCollision {
+                    symbol: label.clone(),
+                    expression: other.to_string(),
+                });
+            }
+        }
+        Ok(labels)
+    }
+}
@@ -0,0 +1,86 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+use normalising::GetNormalisation;
+use reading::{FindWhatReducesToIt, GetDefinition, MaybeString};
+use scoping::{unqualified, SEPARATOR};
+use std::collections::BTreeSet;
+use translating::{StringConcepts, SyntaxFinder};
+
+/// The labels that could complete the symbol being typed in a line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Completion {
+    /// The byte index of the line where the symbol being typed starts.
+    pub start: usize,
+    /// The symbols starting with the symbol being typed, in alphabetical order.
+    pub labels: Vec<String>,
+}
+
+/// Completing partially typed symbols, e.g. for tab completion in an interactive shell.
+pub trait Complete<T>
+where
+    T: GetDefinition + FindWhatReducesToIt + MaybeString,
+    Self: StringConcepts + SyntaxFinder<T> + GetNormalisation,
+{
+    /// Returns the symbols that start with `prefix` and refer to a concept, in alphabetical order. These are the labels of concepts, the labels in the current and opened modules without their module, aliases and labels in the active and fallback languages.
+    fn complete(&self, prefix: &str) -> Vec<String> {
+        let mut symbols: BTreeSet<String> = self.labels_starting_with(&self.normalise(prefix));
+        let mut modules = self.opened_modules();
+        modules.extend(self.get_module());
+        for module in modules {
+            let qualified_prefix = self.normalise(&(module + SEPARATOR + prefix));
+            for label in self.labels_starting_with(&qualified_prefix) {
+                symbols.insert(unqualified(&label).to_string());
+            }
+        }
+        symbols.extend(self.aliases_starting_with(&self.normalise(prefix)));
+        for locale in self.languages() {
+            symbols.extend(self.translations_starting_with(locale, &self.normalise(prefix)));
+        }
+        symbols
+            .into_iter()
+            .filter(|symbol| self.concept_from_label(symbol).is_some())
+            .collect()
+    }
+    /// Returns the strings that start with `prefix` and still label a concept.
+    fn labels_starting_with(&self, prefix: &str) -> BTreeSet<String> {
+        self.string_concepts_starting_with(prefix)
+            .into_iter()
+            .filter(|&(_, concept)| self.get_labellee(concept).is_some())
+            .filter_map(|(_, concept)| self.read_concept(concept).get_string())
+            .collect()
+    }
+    /// Completes the symbol that ends at the cursor, a byte index of `line`. The symbol starts after the last whitespace or parenthesis before the cursor, so the symbols of partially typed expressions such as `let (a (-> b` can be completed.
+    fn complete_at(&self, line: &str, cursor: usize) -> Completion {
+        let before = line.get(..cursor).unwrap_or(line);
+        let start = before
+            .char_indices()
+            .rev()
+            .find(|&(_, c)| c.is_whitespace() || c == '(' || c == ')')
+            .map_or(0, |(i, c)| i + c.len_utf8());
+        Completion {
+            start,
+            labels: self.complete(&before[start..]),
+        }
+    }
+}
+
+impl<S, T> Complete<T> for S
+where
+    T: GetDefinition + FindWhatReducesToIt + MaybeString,
+    S: StringConcepts + SyntaxFinder<T> + GetNormalisation,
+{
+}
@@ -0,0 +1,70 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+use constants::{ADD, FALSE, GREATER, LESS, MULTIPLY, SUBTRACT, TRUE};
+
+/// The result of a built-in operation.
+pub enum Computed {
+    Integer(i64),
+    /// A built-in concept such as `true` or `false`.
+    Concept(usize),
+}
+
+/// Storing whether symbols that are integer literals are read as integer concepts. Otherwise they
+/// are read like any other symbol.
+pub trait Integers {
+    fn reads_integers(&self) -> bool;
+    fn set_reading_integers(&mut self, reading: bool);
+}
+
+/// Returns the value of a symbol that is an integer literal written in its canonical form, e.g. `42` or `-7` but not `+7` or `007`.
+pub fn parse_integer(symbol: &str) -> Option<i64> {
+    match symbol.parse::<i64>() {
+        Ok(n) if n.to_string() == symbol => Some(n),
+        _ => None,
+    }
+}
+
+/// Applies a built-in operator to two integers. Returns `None` if the concept isn't a built-in operator or the result overflows.
+pub fn compute(operator: usize, left: i64, right: i64) -> Option<Computed> {
+    match operator {
+        ADD => left.checked_add(right).map(Computed::Integer),
+        SUBTRACT => left.checked_sub(right).map(Computed::Integer),
+        MULTIPLY => left.checked_mul(right).map(Computed::Integer),
+        LESS => Some(Computed::Concept(if left < right { TRUE } else { FALSE })),
+        GREATER => Some(Computed::Concept(if left > right { TRUE } else { FALSE })),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod integers {
+    use super::parse_integer;
+    #[test]
+    fn canonical_literals() {
+        assert_eq!(parse_integer("42"), Some(42));
+        assert_eq!(parse_integer("-7"), Some(-7));
+        assert_eq!(parse_integer("0"), Some(0));
+    }
+    #[test]
+    fn other_symbols() {
+        assert_eq!(parse_integer("+7"), None);
+        assert_eq!(parse_integer("007"), None);
+        assert_eq!(parse_integer("-0"), None);
+        assert_eq!(parse_integer("-"), None);
+        assert_eq!(parse_integer("99999999999999999999"), None);
+    }
+}
@@ -20,7 +20,10 @@ mod common_part;
 pub use self::abstract_part::AbstractPart;
 pub use self::common_part::CommonPart;
 use errors::{ZiaError, ZiaResult};
-use reading::{FindWhatReducesToIt, GetDefinition, GetDefinitionOf, GetReduction, MaybeString};
+use reading::{
+    FindWhatReducesToIt, GetDefinition, GetDefinitionOf, GetReduction, IsConcrete, MaybeInteger,
+    MaybeString,
+};
 use std::collections::HashSet;
 use writing::{
     MakeReduceFrom, NoLongerReducesFrom, RemoveAsDefinitionOf, RemoveDefinition, RemoveReduction,
@@ -41,6 +44,9 @@ enum SpecificPart {
     Abstract(AbstractPart),
     /// A string concept is associated with a `String` value by the `MaybeString` trait.
     String(String),
+    /// An integer concept is associated with an `i64` value by the `MaybeInteger` trait. Like a
+    /// concrete concept, it cannot be further reduced or defined as a composition.
+    Integer(i64),
 }
 
 impl From<AbstractPart> for Concept {
@@ -132,6 +138,9 @@ impl RemoveDefinition for Concept {
         match self.specific_part {
             SpecificPart::Abstract(ref mut c) => c.remove_definition(),
             SpecificPart::String(_) => panic!("String concepts do not have a definition to remove"),
+            SpecificPart::Integer(_) => {
+                panic!("Integer concepts do not have a definition to remove")
+            }
             SpecificPart::Concrete => {
                 panic!("Concrete concepts do not have a definition to remove")
             }
@@ -165,11 +174,19 @@ impl RemoveReduction for Concept {
         match self.specific_part {
             SpecificPart::Abstract(ref mut c) => c.make_reduce_to_none(),
             SpecificPart::String(_) => panic!("String concepts have no reduction rule to remove"),
+            SpecificPart::Integer(_) => panic!("Integer concepts have no reduction rule to remove"),
             SpecificPart::Concrete => panic!("Concrete concepts have no reduction rule to remove"),
         };
     }
 }
 
+impl IsConcrete for Concept {
+    /// Whether `self` is anything other than an abstract concept.
+    fn is_concrete(&self) -> bool {
+        !matches!(self.specific_part, SpecificPart::Abstract(_))
+    }
+}
+
 impl From<String> for Concept {
     fn from(string: String) -> Concept {
         Concept {
@@ -188,3 +205,22 @@ impl MaybeString for Concept {
         }
     }
 }
+
+impl From<i64> for Concept {
+    fn from(integer: i64) -> Concept {
+        Concept {
+            common_part: CommonPart::default(),
+            specific_part: SpecificPart::Integer(integer),
+        }
+    }
+}
+
+impl MaybeInteger for Concept {
+    /// Gets the `i64` value associated with `self` if it is an integer concept. Otherwise returns `None`.
+    fn get_integer(&self) -> Option<i64> {
+        match self.specific_part {
+            SpecificPart::Integer(n) => Some(n),
+            _ => None,
+        }
+    }
+}
@@ -18,3 +18,31 @@ pub const LABEL: usize = 0;
 pub const DEFINE: usize = 1;
 pub const REDUCTION: usize = 2;
 pub const LET: usize = 3;
+pub const TRACE: usize = 4;
+pub const NORMAL_FORM: usize = 5;
+pub const ADD: usize = 6;
+pub const SUBTRACT: usize = 7;
+pub const MULTIPLY: usize = 8;
+pub const LESS: usize = 9;
+pub const GREATER: usize = 10;
+pub const TRUE: usize = 11;
+pub const FALSE: usize = 12;
+pub const EQUALS: usize = 13;
+pub const REDUCES_FROM: usize = 14;
+pub const EVENTUALLY_REDUCES_FROM: usize = 15;
+pub const USED_IN: usize = 16;
+pub const TRANSITIVELY_USED_IN: usize = 17;
+pub const DELETE: usize = 18;
+pub const CASCADING_DELETE: usize = 19;
+pub const ALIAS: usize = 20;
+pub const UNALIAS: usize = 21;
+pub const PREFER: usize = 22;
+pub const MODULE: usize = 23;
+pub const OPEN: usize = 24;
+pub const PREVIOUS_LABELS: usize = 25;
+pub const FORGET_LABELS: usize = 26;
+pub const SEARCH: usize = 27;
+pub const BUILTIN_COUNT: usize = 28;
+// Indices below this are reserved for built-in concepts, including those added in later versions,
+// so that the indices of concrete concepts added with `ContextBuilder::concrete` don't change.
+pub const RESERVED_BUILTIN_COUNT: usize = 64;
@@ -16,42 +16,147 @@
 */
 
 use adding::{ConceptAdder, StringAdder};
-use reading::ConceptReader;
+use aliasing::Aliases;
+use computing::Integers;
+use diagnosing::{SetWarnings, Warn};
+use errors::{WarningCategory, ZiaError, ZiaResult, ZiaWarning};
+use hosting::{NativeFunction, NativeFunctions};
+use limiting::{Limit, SetLimits};
+use localising::{GetLocale, Locale, SetLocale, Translations};
+use normalising::{find_collision, GetNormalisation, Normalisation, SetNormalisation};
+use output::{Changes, TrackChanges};
+use reading::{ConceptReader, FindWhatReducesToIt, GetDefinition, MaybeString, PatternRules};
 use removing::{BlindConceptRemover, StringRemover};
-use std::collections::HashMap;
-use translating::StringConcept;
+use renaming::LabelHistory;
+use scoping::{unqualified, Modules, Unqualify};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    mem,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+use suggesting::{GetStrictness, SetStrictness, Strictness};
+use tracing::{ReductionStep, Trace};
+use translating::{StringConcept, StringConcepts, SyntaxFinder};
 use writing::ConceptWriter;
 
 /// A container for adding, reading, writing and removing concepts of generic type `T`.
 pub struct Context<T> {
     /// Relates a String value to the index where the concept corresponding to the String is stored
-    /// in the `concepts` field.
-    string_map: HashMap<String, usize>,
+    /// in the `concepts` field. Sorted so that strings can be found by prefix.
+    string_map: BTreeMap<String, usize>,
+    /// Relates the normalised text of an alias to the alias as it was written and the index of the
+    /// concept it refers to.
+    aliases: HashMap<String, (String, usize)>,
+    /// Relates a language and the index of a concept to the label of the concept in that language.
+    translations: HashMap<(Locale, usize), String>,
+    /// Relates a language and the normalised text of a label in that language to the index of the
+    /// concept it refers to.
+    translated: HashMap<(Locale, String), usize>,
+    /// The languages tried after `locale` when reading and displaying labels.
+    fallbacks: Vec<Locale>,
+    /// Relates the index of a concept to the labels it had before it was relabelled, oldest first.
+    previous_labels: HashMap<usize, Vec<String>>,
+    /// Relates the normalised text of a previous label to the index of the concept it labelled.
+    renamed: HashMap<String, usize>,
+    /// Whether symbols that aren't used can be read as the previous label of a concept.
+    resolving_previous_labels: bool,
+    /// Whether symbols that are integer literals are read as integer concepts.
+    reading_integers: bool,
+    /// The previous labels read since they were last taken and the concepts they refer to.
+    renamed_uses: RefCell<Vec<(String, usize)>>,
+    /// The warnings given since they were last taken.
+    warnings: RefCell<Vec<ZiaWarning>>,
+    /// The categories of warnings that aren't given. Implicitly created symbols are suppressed by
+    /// default.
+    suppressed_warnings: HashSet<WarningCategory>,
+    /// Which commands are rejected if they contain symbols that aren't used.
+    strictness: Strictness,
+    /// The module that new labels belong to.
+    module: Option<String>,
+    /// The modules whose labels can be used without qualification, in the order they were opened.
+    opened_modules: Vec<String>,
+    /// Indices of concepts containing pattern variables that have reduction rules, in the order
+    /// they were given a reduction rule, which is the order they are tried in.
+    pattern_rules: Vec<usize>,
     /// Concepts may be stored at an index of this vector as `Some(T)`. If that concept is removed
     /// from the context, `None` will be left at its index.
     concepts: Vec<Option<T>>,
     /// Keeps track of indices of the `concepts` field that have `None`.
     gaps: Vec<usize>,
+    /// The normalisation applied to strings before they are stored in or looked up from
+    /// `string_map`.
+    normalisation: Normalisation,
+    /// The language of error messages.
+    locale: Locale,
+    /// Indices of concepts created since changes were last taken.
+    created: BTreeSet<usize>,
+    /// Indices of concepts, other than those created, written to since changes were last taken.
+    changed: BTreeSet<usize>,
+    /// Indices of concepts removed since changes were last taken.
+    removed: BTreeSet<usize>,
+    /// The reduction steps recorded while tracing, or `None` when not tracing.
+    trace: RefCell<Option<Vec<ReductionStep>>>,
+    /// The maximum number of steps taken to reduce and expand expressions for each command.
+    step_limit: Option<usize>,
+    /// The number of steps taken for the current command.
+    steps: Cell<usize>,
+    /// Evaluation is aborted when this flag is set.
+    cancellation_flag: Option<Arc<AtomicBool>>,
+    /// The functions supplied by the embedder that compute the reductions of concrete concepts.
+    native_functions: HashMap<usize, NativeFunction<Context<T>>>,
 }
 
 impl<T> Default for Context<T> {
     fn default() -> Context<T> {
         Context::<T> {
-            string_map: HashMap::new(),
+            string_map: BTreeMap::new(),
+            aliases: HashMap::new(),
+            translations: HashMap::new(),
+            translated: HashMap::new(),
+            fallbacks: Vec::new(),
+            previous_labels: HashMap::new(),
+            renamed: HashMap::new(),
+            resolving_previous_labels: false,
+            reading_integers: false,
+            renamed_uses: RefCell::new(Vec::new()),
+            warnings: RefCell::new(Vec::new()),
+            suppressed_warnings: [WarningCategory::ImplicitSymbol].iter().cloned().collect(),
+            strictness: Strictness::default(),
+            module: None,
+            opened_modules: Vec::new(),
+            pattern_rules: Vec::new(),
             concepts: Vec::new(),
             gaps: Vec::new(),
+            normalisation: Normalisation::default(),
+            locale: Locale::default(),
+            created: BTreeSet::new(),
+            changed: BTreeSet::new(),
+            removed: BTreeSet::new(),
+            trace: RefCell::new(None),
+            step_limit: None,
+            steps: Cell::new(0),
+            cancellation_flag: None,
+            native_functions: HashMap::new(),
         }
     }
 }
 
 impl<T> StringAdder for Context<T> {
     fn add_string(&mut self, string_id: usize, string: &str) {
-        self.string_map.insert(string.to_string(), string_id);
+        let key = self.normalise(string);
+        self.string_map.insert(key, string_id);
     }
 }
 
 impl<T> ConceptWriter<T> for Context<T> {
     fn write_concept(&mut self, id: usize) -> &mut T {
+        if !self.created.contains(&id) {
+            self.changed.insert(id);
+        }
         match self.concepts[id] {
             Some(ref mut c) => c,
             None => panic!("No concept with id = {}", id),
@@ -66,24 +171,37 @@ impl<T> ConceptReader<T> for Context<T> {
             None => panic!("No concept with id = {}", id),
         }
     }
+    fn has_concept(&self, id: usize) -> bool {
+        self.concepts.get(id).and_then(Option::as_ref).is_some()
+    }
 }
 
 impl<T> BlindConceptRemover for Context<T> {
     fn blindly_remove_concept(&mut self, id: usize) {
         self.concepts[id] = None;
+        self.aliases.retain(|_, &mut (_, concept)| concept != id);
+        self.translations.retain(|&(_, concept), _| concept != id);
+        self.translated.retain(|_, &mut concept| concept != id);
+        self.forget_previous_labels(id);
+        self.remove_pattern_rule(id);
         self.gaps.push(id);
+        self.changed.remove(&id);
+        if !self.created.remove(&id) {
+            self.removed.insert(id);
+        }
     }
 }
 
 impl<T> StringRemover for Context<T> {
     fn remove_string(&mut self, string: &str) {
-        self.string_map.remove(string);
+        let key = self.normalise(string);
+        self.string_map.remove(&key);
     }
 }
 
 impl<T> ConceptAdder<T> for Context<T> {
     fn add_concept(&mut self, concept: T) -> usize {
-        match self.gaps.pop() {
+        let index = match self.gaps.pop() {
             None => {
                 let index = self.concepts.len();
                 self.concepts.push(Some(concept));
@@ -93,15 +211,402 @@ impl<T> ConceptAdder<T> for Context<T> {
                 self.concepts[index] = Some(concept);
                 index
             }
+        };
+        if self.removed.remove(&index) {
+            self.changed.insert(index);
+        } else {
+            self.created.insert(index);
+        }
+        index
+    }
+    fn reserve_indices(&mut self, end: usize) {
+        while self.concepts.len() < end {
+            self.concepts.push(None);
         }
     }
 }
 
 impl<T> StringConcept for Context<T> {
     fn get_string_concept(&self, s: &str) -> Option<usize> {
-        match self.string_map.get(s) {
+        match self.string_map.get(&self.normalise(s)) {
             None => None,
             Some(sr) => Some(*sr),
         }
     }
 }
+
+impl<T> StringConcepts for Context<T> {
+    fn string_concepts(&self) -> Vec<(String, usize)> {
+        self.string_map
+            .iter()
+            .map(|(string, concept)| (string.clone(), *concept))
+            .collect()
+    }
+    fn string_concepts_starting_with(&self, prefix: &str) -> Vec<(String, usize)> {
+        self.string_map
+            .range(prefix.to_string()..)
+            .take_while(|&(string, _)| string.starts_with(prefix))
+            .map(|(string, concept)| (string.clone(), *concept))
+            .collect()
+    }
+}
+
+impl<T> GetNormalisation for Context<T> {
+    fn get_normalisation(&self) -> Normalisation {
+        self.normalisation
+    }
+}
+
+impl<T: MaybeString> SetNormalisation for Context<T> {
+    /// Changes the normalisation and renormalises the strings that have already been stored.
+    fn set_normalisation(&mut self, normalisation: Normalisation) -> ZiaResult<()> {
+        let strings = self
+            .string_map
+            .values()
+            .filter_map(|&id| self.read_concept(id).get_string())
+            .collect::<Vec<String>>();
+        let mut aliases = self
+            .aliases
+            .values()
+            .map(|a| a.0.as_str())
+            .collect::<Vec<_>>();
+        aliases.sort();
+        let collision = find_collision(normalisation, strings.iter().map(|s| s.as_str()))
+            .or_else(|| find_collision(normalisation, aliases.into_iter()));
+        if let Some((first, second)) = collision {
+            return Err(ZiaError::NormalisationCollision { first, second });
+        }
+        self.normalisation = normalisation;
+        let ids = mem::take(&mut self.string_map)
+            .into_values()
+            .collect::<Vec<usize>>();
+        for id in ids {
+            if let Some(string) = self.read_concept(id).get_string() {
+                self.string_map.insert(normalisation.normalise(&string), id);
+            }
+        }
+        let aliases = self
+            .aliases
+            .drain()
+            .map(|(_, alias)| alias)
+            .collect::<Vec<_>>();
+        for (alias, concept) in aliases {
+            self.aliases
+                .insert(normalisation.normalise(&alias), (alias, concept));
+        }
+        self.translated = self
+            .translations
+            .iter()
+            .map(|(&(locale, concept), label)| ((locale, normalisation.normalise(label)), concept))
+            .collect();
+        self.renamed = HashMap::new();
+        for (&concept, labels) in &self.previous_labels {
+            for label in labels {
+                self.renamed.insert(normalisation.normalise(label), concept);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> Aliases for Context<T> {
+    fn add_alias(&mut self, alias: &str, concept: usize) {
+        let key = self.normalise(alias);
+        self.aliases.insert(key, (alias.to_string(), concept));
+        if !self.created.contains(&concept) {
+            self.changed.insert(concept);
+        }
+    }
+    fn remove_alias(&mut self, alias: &str) -> Option<usize> {
+        let key = self.normalise(alias);
+        let concept = self.aliases.remove(&key).map(|(_, concept)| concept);
+        if let Some(c) = concept {
+            if !self.created.contains(&c) {
+                self.changed.insert(c);
+            }
+        }
+        concept
+    }
+    fn get_aliased(&self, alias: &str) -> Option<usize> {
+        self.aliases
+            .get(&self.normalise(alias))
+            .map(|&(_, concept)| concept)
+    }
+    fn get_aliases(&self, concept: usize) -> Vec<String> {
+        let mut aliases: Vec<String> = self
+            .aliases
+            .values()
+            .filter(|&&(_, c)| c == concept)
+            .map(|(alias, _)| alias.clone())
+            .collect();
+        aliases.sort();
+        aliases
+    }
+    fn aliases_starting_with(&self, prefix: &str) -> Vec<String> {
+        self.aliases
+            .iter()
+            .filter(|&(key, _)| key.starts_with(prefix))
+            .map(|(_, (alias, _))| alias.clone())
+            .collect()
+    }
+}
+
+impl<T> Translations for Context<T> {
+    fn add_translation(&mut self, locale: Locale, concept: usize, label: &str) {
+        let key = self.normalise(label);
+        self.translated.insert((locale, key), concept);
+        self.translations
+            .insert((locale, concept), label.to_string());
+        if !self.created.contains(&concept) {
+            self.changed.insert(concept);
+        }
+    }
+    fn remove_translation(&mut self, locale: Locale, concept: usize) -> Option<String> {
+        let label = self.translations.remove(&(locale, concept));
+        if let Some(ref l) = label {
+            let key = self.normalise(l);
+            self.translated.remove(&(locale, key));
+            if !self.created.contains(&concept) {
+                self.changed.insert(concept);
+            }
+        }
+        label
+    }
+    fn get_translation(&self, locale: Locale, concept: usize) -> Option<String> {
+        self.translations.get(&(locale, concept)).cloned()
+    }
+    fn get_translated(&self, locale: Locale, label: &str) -> Option<usize> {
+        self.translated
+            .get(&(locale, self.normalise(label)))
+            .cloned()
+    }
+    fn translations_starting_with(&self, locale: Locale, prefix: &str) -> Vec<String> {
+        self.translations
+            .iter()
+            .filter(|&(&(l, _), label)| l == locale && self.normalise(label).starts_with(prefix))
+            .map(|(_, label)| label.clone())
+            .collect()
+    }
+    fn get_fallbacks(&self) -> Vec<Locale> {
+        self.fallbacks.clone()
+    }
+    fn set_fallbacks(&mut self, fallbacks: Vec<Locale>) {
+        self.fallbacks = fallbacks;
+    }
+}
+
+impl<T> Integers for Context<T> {
+    fn reads_integers(&self) -> bool {
+        self.reading_integers
+    }
+    fn set_reading_integers(&mut self, reading: bool) {
+        self.reading_integers = reading;
+    }
+}
+
+impl<T> PatternRules for Context<T> {
+    fn get_pattern_rules(&self) -> &[usize] {
+        &self.pattern_rules
+    }
+    fn add_pattern_rule(&mut self, concept: usize) {
+        if !self.pattern_rules.contains(&concept) {
+            self.pattern_rules.push(concept);
+        }
+    }
+    fn remove_pattern_rule(&mut self, concept: usize) {
+        self.pattern_rules.retain(|&c| c != concept);
+    }
+}
+
+impl<T> LabelHistory for Context<T> {
+    fn add_previous_label(&mut self, concept: usize, label: &str) {
+        let key = self.normalise(label);
+        self.renamed.insert(key, concept);
+        self.previous_labels
+            .entry(concept)
+            .or_default()
+            .push(label.to_string());
+    }
+    fn get_previous_labels(&self, concept: usize) -> Vec<String> {
+        self.previous_labels
+            .get(&concept)
+            .cloned()
+            .unwrap_or_default()
+    }
+    fn forget_previous_labels(&mut self, concept: usize) -> Vec<String> {
+        let labels = self.previous_labels.remove(&concept).unwrap_or_default();
+        if !labels.is_empty() {
+            self.renamed.retain(|_, &mut c| c != concept);
+            if !self.created.contains(&concept) {
+                self.changed.insert(concept);
+            }
+        }
+        labels
+    }
+    fn get_renamed(&self, label: &str) -> Option<usize> {
+        self.renamed.get(&self.normalise(label)).cloned()
+    }
+    fn forget_renamed(&mut self, label: &str) {
+        let key = self.normalise(label);
+        self.renamed.remove(&key);
+        self.warnings.borrow_mut().retain(|warning| match *warning {
+            ZiaWarning::RenamedLabel { ref old, .. } => old != label,
+            _ => true,
+        });
+    }
+    fn resolves_previous_labels(&self) -> bool {
+        self.resolving_previous_labels
+    }
+    fn set_resolving_previous_labels(&mut self, resolving: bool) {
+        self.resolving_previous_labels = resolving;
+    }
+    fn record_renamed_use(&self, label: &str, concept: usize) {
+        self.renamed_uses
+            .borrow_mut()
+            .push((label.to_string(), concept));
+    }
+    fn take_renamed_uses(&self) -> Vec<(String, usize)> {
+        self.renamed_uses.replace(Vec::new())
+    }
+}
+
+impl<T> Warn for Context<T> {
+    fn warn(&self, warning: ZiaWarning) {
+        if !self.suppressed_warnings.contains(&warning.category()) {
+            self.warnings.borrow_mut().push(warning);
+        }
+    }
+    fn take_warnings(&self) -> Vec<ZiaWarning> {
+        self.warnings.replace(Vec::new())
+    }
+}
+
+impl<T> SetWarnings for Context<T> {
+    fn suppress_warnings(&mut self, category: WarningCategory) {
+        self.suppressed_warnings.insert(category);
+    }
+    fn allow_warnings(&mut self, category: WarningCategory) {
+        self.suppressed_warnings.remove(&category);
+    }
+}
+
+impl<T> GetStrictness for Context<T> {
+    fn get_strictness(&self) -> Strictness {
+        self.strictness
+    }
+}
+
+impl<T> SetStrictness for Context<T> {
+    fn set_strictness(&mut self, strictness: Strictness) {
+        self.strictness = strictness;
+    }
+}
+
+impl<T> Modules for Context<T> {
+    fn get_module(&self) -> Option<String> {
+        self.module.clone()
+    }
+    fn set_module(&mut self, module: Option<String>) {
+        self.module = module;
+    }
+    fn open_module(&mut self, module: &str) {
+        if !self.opened_modules.iter().any(|m| m == module) {
+            self.opened_modules.push(module.to_string());
+        }
+    }
+    fn opened_modules(&self) -> Vec<String> {
+        self.opened_modules.clone()
+    }
+}
+
+impl<T> Unqualify for Context<T>
+where
+    T: FindWhatReducesToIt + GetDefinition,
+{
+    fn unqualify(&self, label: String) -> String {
+        let symbol = unqualified(&label).to_string();
+        if symbol != label && self.concept_from_label(&symbol) == self.concept_from_label(&label) {
+            symbol
+        } else {
+            label
+        }
+    }
+}
+
+impl<T> TrackChanges for Context<T> {
+    fn take_changes(&mut self) -> Changes {
+        Changes {
+            created: self.created.split_off(&0).into_iter().collect(),
+            changed: self.changed.split_off(&0).into_iter().collect(),
+            removed: self.removed.split_off(&0).into_iter().collect(),
+        }
+    }
+}
+
+impl<T> GetLocale for Context<T> {
+    fn get_locale(&self) -> Locale {
+        self.locale
+    }
+}
+
+impl<T> SetLocale for Context<T> {
+    fn set_locale(&mut self, locale: Locale) {
+        self.locale = locale;
+    }
+}
+
+impl<T> Trace for Context<T> {
+    fn start_tracing(&self) {
+        *self.trace.borrow_mut() = Some(Vec::new());
+    }
+    fn is_tracing(&self) -> bool {
+        self.trace.borrow().is_some()
+    }
+    fn record_step(&self, step: ReductionStep) {
+        if let Some(ref mut steps) = *self.trace.borrow_mut() {
+            steps.push(step);
+        }
+    }
+    fn stop_tracing(&self) -> Vec<ReductionStep> {
+        self.trace.borrow_mut().take().unwrap_or_default()
+    }
+}
+
+impl<T> Limit for Context<T> {
+    fn reset_steps(&self) {
+        self.steps.set(0);
+    }
+    fn take_step(&self) -> bool {
+        let steps = self.steps.get() + 1;
+        let cancelled = match self.cancellation_flag {
+            Some(ref flag) => flag.load(Ordering::Relaxed),
+            None => false,
+        };
+        let allowed = !cancelled && self.step_limit.is_none_or(|limit| steps <= limit);
+        if allowed {
+            self.steps.set(steps);
+        }
+        allowed
+    }
+    fn steps_taken(&self) -> usize {
+        self.steps.get()
+    }
+}
+
+impl<T> SetLimits for Context<T> {
+    fn set_step_limit(&mut self, limit: Option<usize>) {
+        self.step_limit = limit;
+    }
+    fn set_cancellation_flag(&mut self, flag: Option<Arc<AtomicBool>>) {
+        self.cancellation_flag = flag;
+    }
+}
+
+impl<T> NativeFunctions for Context<T> {
+    fn add_native_function(&mut self, concept: usize, function: NativeFunction<Context<T>>) {
+        self.native_functions.insert(concept, function);
+    }
+    fn get_native_function(&self, concept: usize) -> Option<NativeFunction<Context<T>>> {
+        self.native_functions.get(&concept).cloned()
+    }
+}
@@ -0,0 +1,30 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+use errors::{WarningCategory, ZiaWarning};
+
+/// Collecting the warnings given while a command is executed.
+pub trait Warn {
+    /// Keeps the warning unless its category is suppressed.
+    fn warn(&self, warning: ZiaWarning);
+    fn take_warnings(&self) -> Vec<ZiaWarning>;
+}
+
+/// Choosing which categories of warnings are given.
+pub trait SetWarnings {
+    fn suppress_warnings(&mut self, category: WarningCategory);
+    fn allow_warnings(&mut self, category: WarningCategory);
+}
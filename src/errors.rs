@@ -14,12 +14,13 @@ GNU General Public License for more details.
 You should have received a copy of the GNU General Public License
 along with this program. If not, see <http://www.gnu.org/licenses/>.*/
 
-use std::fmt;
+use localising::Locale;
+use std::{error::Error, fmt};
 
 pub type ZiaResult<T> = Result<T, ZiaError>;
 
 /// All the expected ways a Zia command could be invalid.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum ZiaError {
     /// When specifying a reduction rule that already exists.
     RedundantReduction,
@@ -33,44 +34,148 @@ pub enum ZiaError {
     NotAProgram,
     /// When defining an expanded expression.
     BadDefinition,
-    /// When the command would complete a cycle of chained reduction rules.
-    CyclicReduction,
-    /// When a concept is contained within the concept that it reduces to.  
-    ExpandingReduction,
+    /// When the command would complete a cycle of chained reduction rules. The chain starts and ends with the same concept.
+    CyclicReduction { chain: Vec<String> },
+    /// When a concept is contained within the concept that it reduces to.
+    ExpandingReduction { concept: String, reduction: String },
     /// When a concept is contained within its definition.
     InfiniteDefinition,
     /// When a command contains a pair of parentheses with no syntax inside.
     EmptyParentheses,
     /// When the interpreter cannot determine the tree structure of an expression.
     AmbiguousExpression,
+    /// When a programmatically built expression contains a symbol that is empty or contains whitespace or parentheses.
+    InvalidSymbol,
     /// When trying to refactor a used symbol as another used symbol or expression.
-    DefinitionCollision,
+    DefinitionCollision { symbol: String, expression: String },
     /// When trying to define the composition of a concrete concept.
     SettingDefinitionOfConcrete,
     /// When trying to specify a reduction rule for a concrete concept.
     ConcreteReduction,
     /// When trying to specify a reduction rule for a concept whose components reduce to something else.
-    MultipleReductionPaths,
+    MultipleReductionPaths {
+        concept: String,
+        components: Vec<String>,
+    },
+    /// When reducing or expanding an expression takes more steps than allowed or is cancelled.
+    ReductionLimitExceeded { steps: usize, expression: String },
+    /// When the reduction of a pattern rule contains a pattern variable that isn't in the pattern.
+    UnboundVariable { variable: String, pattern: String },
+    /// When deleting a concept that compositions or reduction rules of other concepts still refer to.
+    DependentConcepts {
+        concept: String,
+        dependents: Vec<String>,
+    },
+    /// When trying to delete a concrete concept.
+    DeletingConcrete,
+    /// When deleting a symbol that hasn't been used.
+    RedundantDeletion,
+    /// When specifying an alias that already exists.
+    RedundantAlias,
+    /// When removing or preferring a symbol that isn't an alias.
+    UnknownAlias { symbol: String },
+    /// When labelling a concept in a language with the label it already has in that language.
+    RedundantTranslation,
+    /// When a command contains a symbol that isn't used and the context is strict about it. The closest existing labels are suggested.
+    UnknownSymbol {
+        symbol: String,
+        suggestions: Vec<String>,
+    },
+    /// When registering a native function under a label that already refers to a concept.
+    UsedLabel { label: String, concept: String },
+    /// When changing the normalisation would make two different labels or aliases the same.
+    NormalisationCollision { first: String, second: String },
+}
+
+impl ZiaError {
+    /// A code that identifies the kind of error and won't change between versions.
+    pub fn code(&self) -> &'static str {
+        match *self {
+            ZiaError::RedundantReduction => "Z0001",
+            ZiaError::RedundantDefinition => "Z0002",
+            ZiaError::RedundantRefactor => "Z0003",
+            ZiaError::RedundantDefinitionRemoval => "Z0004",
+            ZiaError::NotAProgram => "Z0005",
+            ZiaError::BadDefinition => "Z0006",
+            ZiaError::CyclicReduction { .. } => "Z0007",
+            ZiaError::ExpandingReduction { .. } => "Z0008",
+            ZiaError::InfiniteDefinition => "Z0009",
+            ZiaError::EmptyParentheses => "Z0010",
+            ZiaError::AmbiguousExpression => "Z0011",
+            ZiaError::DefinitionCollision { .. } => "Z0012",
+            ZiaError::SettingDefinitionOfConcrete => "Z0013",
+            ZiaError::ConcreteReduction => "Z0014",
+            ZiaError::MultipleReductionPaths { .. } => "Z0015",
+            ZiaError::InvalidSymbol => "Z0016",
+            ZiaError::ReductionLimitExceeded { .. } => "Z0017",
+            ZiaError::UnboundVariable { .. } => "Z0018",
+            ZiaError::DependentConcepts { .. } => "Z0019",
+            ZiaError::DeletingConcrete => "Z0020",
+            ZiaError::RedundantDeletion => "Z0021",
+            ZiaError::RedundantAlias => "Z0022",
+            ZiaError::UnknownAlias { .. } => "Z0023",
+            ZiaError::RedundantTranslation => "Z0024",
+            ZiaError::UnknownSymbol { .. } => "Z0025",
+            ZiaError::UsedLabel { .. } => "Z0026",
+            ZiaError::NormalisationCollision { .. } => "Z0027",
+        }
+    }
+}
+
+/// Ways a valid Zia command might not do what was intended.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ZiaWarning {
+    /// When a symbol that wasn't used before is given to a new concept, e.g. because of a typo.
+    ImplicitSymbol { symbol: String },
+    /// When a symbol is read as the previous label of a concept that has since been relabelled.
+    RenamedLabel { old: String, new: String },
+    /// When a pattern rule will never be used because every instance of it is an instance of another pattern rule that is tried first.
+    UnreachableRule {
+        rule: String,
+        shadowing_rule: String,
+    },
+}
+
+impl ZiaWarning {
+    /// A code that identifies the kind of warning and won't change between versions.
+    pub fn code(&self) -> &'static str {
+        match *self {
+            ZiaWarning::ImplicitSymbol { .. } => "W0001",
+            ZiaWarning::RenamedLabel { .. } => "W0002",
+            ZiaWarning::UnreachableRule { .. } => "W0003",
+        }
+    }
+    /// The category that can be suppressed to stop this warning from being given.
+    pub fn category(&self) -> WarningCategory {
+        match *self {
+            ZiaWarning::ImplicitSymbol { .. } => WarningCategory::ImplicitSymbol,
+            ZiaWarning::RenamedLabel { .. } => WarningCategory::RenamedLabel,
+            ZiaWarning::UnreachableRule { .. } => WarningCategory::UnreachableRule,
+        }
+    }
+}
+
+/// The kinds of warnings that can be suppressed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WarningCategory {
+    /// Suppressed by default because most commands that specify rules use new symbols.
+    ImplicitSymbol,
+    RenamedLabel,
+    UnreachableRule,
+}
+
+impl fmt::Display for ZiaWarning {
+    /// Displays the message explaining the warning in English.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", Locale::English.describe_warning(self))
+    }
 }
 
 impl fmt::Display for ZiaError {
+    /// Displays the message explaining the error in English.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", match *self {
-	        ZiaError::RedundantReduction => "That reduction rule already exists.",
-			ZiaError::RedundantDefinition => "That definition already exists.",
-			ZiaError::RedundantRefactor => "Relabelling something that doesn't yet exist has no effect.",
-			ZiaError::RedundantDefinitionRemoval => "Removing a definition that doesn't exist is redundant.",
-	        ZiaError::NotAProgram => "No program exists for this syntax.",
-	        ZiaError::BadDefinition => "Cannot define expressions.",
-	        ZiaError::CyclicReduction => "Cannot allow a chain of reduction rules to loop.",
-	        ZiaError::ExpandingReduction => "Cannot reduce a concept to an expression containing itself.",
-	        ZiaError::InfiniteDefinition => "Cannot define a concept as an expression containing itself.",
-			ZiaError::EmptyParentheses => "Parentheses need to contain a symbol or expression.",
-			ZiaError::AmbiguousExpression => "Ambiguity due to lack of precedence or associativity defined for the symbols in that expression.",
-			ZiaError::DefinitionCollision => "Cannot define a used symbol as another used symbol or expression.",
-			ZiaError::SettingDefinitionOfConcrete => "Cannot set a definition of a concrete concept",
-			ZiaError::ConcreteReduction => "Cannot reduce a concrete concept", 
-			ZiaError::MultipleReductionPaths => "Concept is already composed of concepts with their own reduction rules."
-	    })
+        write!(f, "{}", Locale::English.describe(self))
     }
 }
+
+impl Error for ZiaError {}
@@ -0,0 +1,95 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+use adding::Labeller;
+use ast::Expr;
+use errors::{ZiaError, ZiaResult};
+use reading::{
+    Display, FindWhatReducesToIt, GetDefinition, GetDefinitionOf, GetReduction, MaybeString,
+};
+use std::rc::Rc;
+use translating::{is_valid_symbol, SyntaxFinder};
+use writing::{MakeReduceFrom, SetAsDefinitionOf, SetDefinition, SetReduction};
+
+/// A function supplied by the embedder that computes the reduction of a concept. It is given the
+/// context and the concepts that the concept is applied to, e.g. `[a, b]` for `(f a) b`, and
+/// returns `Ok(None)` if the concept doesn't reduce when applied to these concepts.
+pub type NativeFunction<S> = Rc<dyn Fn(&S, &[usize]) -> ZiaResult<Option<Expr>>>;
+
+/// Storing and calling the native functions of concepts.
+pub trait NativeFunctions
+where
+    Self: Sized,
+{
+    fn add_native_function(&mut self, concept: usize, function: NativeFunction<Self>);
+    fn get_native_function(&self, concept: usize) -> Option<NativeFunction<Self>>;
+}
+
+/// Registering functions supplied by the embedder to compute the reductions of new concrete concepts.
+pub trait RegisterNative<T>
+where
+    T: SetReduction
+        + MakeReduceFrom
+        + From<String>
+        + GetDefinitionOf
+        + SetDefinition
+        + SetAsDefinitionOf
+        + GetReduction
+        + GetDefinition
+        + MaybeString
+        + FindWhatReducesToIt
+        + From<Self::C>
+        + From<Self::A>,
+    Self: Labeller<T> + SyntaxFinder<T> + NativeFunctions + Display<T>,
+{
+    /// Adds a concrete concept labelled with `label` whose reduction, and the reduction of compositions with it on the left, is computed by `function`. Returns the index of the concept.
+    fn register_native<F>(&mut self, label: &str, function: F) -> ZiaResult<usize>
+    where
+        F: Fn(&Self, &[usize]) -> ZiaResult<Option<Expr>> + 'static,
+    {
+        if !is_valid_symbol(label) {
+            return Err(ZiaError::InvalidSymbol);
+        }
+        if let Some(used) = self.concept_from_label(label) {
+            return Err(ZiaError::UsedLabel {
+                label: label.to_string(),
+                concept: self.display(used),
+            });
+        }
+        let concept = self.new_default::<Self::C>();
+        try!(self.label(concept, label));
+        self.add_native_function(concept, Rc::new(function));
+        Ok(concept)
+    }
+}
+
+impl<S, T> RegisterNative<T> for S
+where
+    T: SetReduction
+        + MakeReduceFrom
+        + From<String>
+        + GetDefinitionOf
+        + SetDefinition
+        + SetAsDefinitionOf
+        + GetReduction
+        + GetDefinition
+        + MaybeString
+        + FindWhatReducesToIt
+        + From<Self::C>
+        + From<Self::A>,
+    S: Labeller<T> + SyntaxFinder<T> + NativeFunctions + Display<T>,
+{
+}
@@ -18,9 +18,21 @@
 /// Traits for adding concepts to the context.
 mod adding;
 
+/// Symbols that refer to concepts besides their labels.
+mod aliasing;
+
 /// Abstract syntax tree. Relates syntax to concepts.
 mod ast;
 
+/// Configuring the construction of a context.
+mod building;
+
+/// Completing partially typed symbols.
+mod completing;
+
+/// Built-in operations whose reductions are computed natively.
+mod computing;
+
 /// The units that make up the context. Defined in terms of their relationship with other concepts.
 mod concepts;
 
@@ -30,35 +42,91 @@ mod constants;
 /// The container of concepts that coordinates adding, reading, writing and removing of concepts.
 mod context;
 
+/// Warnings about commands that are valid but might not do what was intended.
+mod diagnosing;
+
 /// The errors that the users could make when making commands.
 mod errors;
 
+/// Limiting how much work evaluating a command may do.
+mod limiting;
+
+/// Functions supplied by the embedder to compute the reductions of concepts.
+mod hosting;
+
+/// Catalogues of built-in labels and error messages in different languages.
+mod localising;
+
+/// Unicode normalisation of the text of symbols.
+mod normalising;
+
+/// What successfully executed commands did.
+mod output;
+
 /// Traits for reading concepts within the context.
 mod reading;
 
 /// Traits for removing concepts from the context.
 mod removing;
 
+/// The labels that concepts had before they were relabelled.
+mod renaming;
+
+/// Modules that qualify labels and the scope of unqualified symbols.
+mod scoping;
+
+/// Searching for labels matching a pattern.
+mod searching;
+
+/// Suggesting labels similar to symbols that aren't used.
+mod suggesting;
+
 /// Traits for the context to translate strings into abstract syntax trees.
 mod translating;
 
+/// Recording the steps taken to reduce expressions.
+mod tracing;
+
 /// Traits for writing concepts within the context.
 mod writing;
 
 pub use adding::ContextMaker;
 use adding::{ConceptMaker, Container, ExecuteReduction, FindOrInsertDefinition, Labeller};
-pub use ast::SyntaxTree;
+use aliasing::Aliaser;
+pub use ast::{Expr, SyntaxTree};
+pub use building::{Builtin, ContextBuilder, Feature};
+pub use completing::{Complete, Completion};
+use computing::parse_integer;
 use concepts::{AbstractPart, CommonPart, Concept};
-use constants::{LABEL, DEFINE, LET, REDUCTION};
+use constants::{
+    ALIAS, CASCADING_DELETE, DEFINE, DELETE, EVENTUALLY_REDUCES_FROM, FORGET_LABELS, LABEL, LET,
+    MODULE, NORMAL_FORM, OPEN, PREFER, PREVIOUS_LABELS, REDUCES_FROM, REDUCTION, SEARCH, TRACE,
+    TRANSITIVELY_USED_IN, UNALIAS, USED_IN,
+};
 use context::Context as GenericContext;
-pub use errors::ZiaError;
+pub use diagnosing::SetWarnings;
 use errors::ZiaResult;
+pub use errors::{WarningCategory, ZiaError, ZiaWarning};
+pub use hosting::RegisterNative;
+pub use limiting::SetLimits;
+use localising::GetLocale;
+pub use localising::{Locale, Multilingual};
+pub use normalising::{Normalisation, SetNormalisation};
+use output::{print_results, Collector, TrackChanges};
+pub use output::{Changes, Output, Report, Sink, WriteSink};
+pub use reading::Steps;
 use reading::{
-    DisplayJoint, FindWhatReducesToIt, GetDefinition, GetDefinitionOf, GetLabel, GetReduction,
-    MaybeConcept, MaybeString, MightExpand, Pair, SyntaxReader,
+    is_variable_symbol, DisplayJoint, FindWhatReducesToIt, GetDefinition, GetDefinitionOf,
+    GetLabel, GetReduction, IsConcrete, MaybeConcept, MaybeInteger, MaybeString, MightExpand, Pair,
+    SyntaxReader,
 };
-use removing::DefinitionDeleter;
-use std::rc::Rc;
+use removing::{ConceptDeleter, DefinitionDeleter};
+use scoping::{global, SEPARATOR};
+pub use searching::{ConceptKind, LabelSearch, PatternSyntax, SearchLabels};
+use std::{collections::HashSet, rc::Rc};
+use suggesting::{GetStrictness, MIN_SIMILAR_SYMBOL_LENGTH};
+pub use suggesting::{SetStrictness, Strictness, SuggestLabels};
+pub use tracing::ReductionStep;
 use translating::SyntaxConverter;
 use writing::{
     MakeReduceFrom, NoLongerReducesFrom, RemoveAsDefinitionOf, RemoveDefinition, RemoveReduction,
@@ -68,11 +136,19 @@ use writing::{
 /// A container for adding, writing, reading and removing `Concept`s.
 pub type Context = GenericContext<Concept>;
 
+impl ContextBuilder {
+    /// Constructs a `Context` with this configuration.
+    pub fn build(&self) -> ZiaResult<Context> {
+        Context::from_builder(self)
+    }
+}
+
 /// Executing a command based on a string to add, write, read, or remove contained concepts.  
 pub trait Execute<T>
 where
-    Self: Call<T> + SyntaxConverter<T>,
+    Self: Call<T> + SyntaxConverter<T> + GetLocale + GetStrictness + SuggestLabels<T>,
     T: From<String>
+        + From<i64>
         + From<Self::C>
         + From<Self::A>
         + RemoveDefinition
@@ -85,6 +161,8 @@ where
         + SetAsDefinitionOf
         + GetDefinition
         + MaybeString
+        + MaybeInteger
+        + IsConcrete
         + GetDefinitionOf
         + GetReduction
         + FindWhatReducesToIt,
@@ -95,21 +173,164 @@ where
         + DisplayJoint
         + PartialEq<Self::S>,
 {
+    /// Executes a command and returns a `String` that may be empty, an error message in the language of the context, printed text or the value of an expression. Warnings are given on separate lines before any printed text or value.
     fn execute(&mut self, command: &str) -> String {
-        let ast = match self.ast_from_expression(command) {
-            Ok(a) => a,
-            Err(e) => return e.to_string(),
+        match self.execute_with_warnings(command) {
+            Ok(r) => self.describe_report(&r),
+            Err(e) => self.get_locale().describe(&e),
+        }
+    }
+    /// Executes a command built with `Expr` in the same way as `execute` executes a command given as a string.
+    fn execute_ast(&mut self, expr: &Expr) -> String {
+        match self.execute_ast_with_warnings(expr) {
+            Ok(r) => self.describe_report(&r),
+            Err(e) => self.get_locale().describe(&e),
+        }
+    }
+    /// Executes a command and returns whether it modified the context, printed text or expressed a value.
+    fn execute_structured(&mut self, command: &str) -> ZiaResult<Output> {
+        self.execute_with_warnings(command).map(|r| r.output)
+    }
+    /// Executes a command built with `Expr` in the same way as `execute_structured` executes a command given as a string.
+    fn execute_structured_ast(&mut self, expr: &Expr) -> ZiaResult<Output> {
+        self.execute_ast_with_warnings(expr).map(|r| r.output)
+    }
+    /// Executes a command in the same way as `execute_structured` and also returns the warnings it gave.
+    fn execute_with_warnings(&mut self, command: &str) -> ZiaResult<Report> {
+        let ast = try!(self.ast_from_expression(command));
+        self.collect_outputs(&ast)
+    }
+    /// Executes a command built with `Expr` in the same way as `execute_with_warnings` executes a command given as a string.
+    fn execute_ast_with_warnings(&mut self, expr: &Expr) -> ZiaResult<Report> {
+        let ast = try!(self.ast_from_expr(expr));
+        self.collect_outputs(&ast)
+    }
+    /// Executes a command, writing each text it prints and each value it expresses to the sink, and returns the changes it made to the context.
+    fn execute_to(&mut self, command: &str, sink: &mut dyn Sink) -> ZiaResult<Changes> {
+        let ast = try!(self.ast_from_expression(command));
+        self.execute_syntax(&ast, sink)
+    }
+    /// Executes a command built with `Expr` in the same way as `execute_to` executes a command given as a string.
+    fn execute_ast_to(&mut self, expr: &Expr, sink: &mut dyn Sink) -> ZiaResult<Changes> {
+        let ast = try!(self.ast_from_expr(expr));
+        self.execute_syntax(&ast, sink)
+    }
+    /// Returns the label of a concept, the expression it is defined as if unlabelled, or its value in quotes if it is a string concept. Useful for native functions, which are given the indices of concepts. Returns `None` if there is no concept with this index or it can't be displayed because it is unlabelled and has no definition.
+    fn display_concept(&self, concept: usize) -> Option<String> {
+        self.try_display(concept)
+    }
+    /// Reduces an expression by the number of steps and returns the reduced expression.
+    fn reduce_expression(&self, expression: &str, steps: Steps) -> ZiaResult<String> {
+        let ast = try!(self.ast_from_expression::<Self::S>(expression));
+        self.take_renamed_uses();
+        self.reset_steps();
+        self.reduce_steps(&ast, steps).map(|r| r.to_string())
+    }
+    /// Reduces an expression to its normal form and returns each rewrite made by a reduction rule.
+    fn trace_reduction(&self, expression: &str) -> ZiaResult<Vec<ReductionStep>> {
+        let ast = try!(self.ast_from_expression::<Self::S>(expression));
+        self.take_renamed_uses();
+        self.reset_steps();
+        self.trace_reduction_of(&ast)
+    }
+    /// Calls the syntax of a command, only tracking the changes made and the warnings given by this command. A warning is also given for each previous label read in the command. Warnings are written to the sink once the command has been executed.
+    fn execute_syntax(&mut self, ast: &Rc<Self::S>, sink: &mut dyn Sink) -> ZiaResult<Changes> {
+        self.take_changes();
+        self.take_warnings();
+        self.reset_steps();
+        for (label, concept) in self.take_renamed_uses() {
+            self.warn(ZiaWarning::RenamedLabel {
+                old: label,
+                new: self.display(concept),
+            });
+        }
+        try!(self.check_symbols(ast));
+        try!(self.call(ast, sink));
+        for warning in self.take_warnings() {
+            sink.warn(&warning, &self.get_locale().describe_warning(&warning));
+        }
+        Ok(self.take_changes())
+    }
+    /// In a strict context, rejects a query containing a symbol that isn't used or, if commands are also checked, another command containing a new symbol that is similar to an existing label and isn't too short for the similarity to suggest a typo. Commands given names or patterns rather than concepts, such as `module`, `unalias` and `search`, aren't checked.
+    fn check_symbols(&self, ast: &Rc<Self::S>) -> ZiaResult<()> {
+        let strictness = self.get_strictness();
+        if strictness == Strictness::Lenient {
+            return Ok(());
+        }
+        let command = match ast.get_expansion() {
+            Some((ref left, _)) => left.get_concept(),
+            None => None,
         };
-        match self.call(&ast) {
-            Ok(s) => s,
-            Err(e) => e.to_string(),
+        let is_query = match command {
+            Some(MODULE) | Some(OPEN) | Some(UNALIAS) | Some(PREFER) | Some(SEARCH) => {
+                return Ok(())
+            }
+            Some(LET) | Some(DELETE) | Some(CASCADING_DELETE) | Some(FORGET_LABELS) => false,
+            _ => true,
+        };
+        if !is_query && strictness == Strictness::Queries {
+            return Ok(());
+        }
+        for symbol in self.unknown_symbols(ast) {
+            let suggestions = self.suggest_labels(&symbol);
+            if is_query
+                || (!suggestions.is_empty() && symbol.chars().count() >= MIN_SIMILAR_SYMBOL_LENGTH)
+            {
+                return Err(ZiaError::UnknownSymbol {
+                    symbol,
+                    suggestions,
+                });
+            }
+        }
+        Ok(())
+    }
+    /// Returns the symbols of the syntax that aren't used, from left to right. Pattern variables, the number of steps of a reduction operator and, if integers are read, integer literals are never unknown.
+    fn unknown_symbols(&self, ast: &Rc<Self::S>) -> Vec<String> {
+        match ast.get_expansion() {
+            Some(_) if self.reduction_operator(ast).is_some() => Vec::new(),
+            Some((ref left, ref right)) => {
+                let mut symbols = self.unknown_symbols(left);
+                symbols.extend(self.unknown_symbols(right));
+                symbols
+            }
+            None if ast.get_concept().is_none() => {
+                let symbol = ast.to_string();
+                if is_variable_symbol(&symbol)
+                    || (self.reads_integers() && parse_integer(&symbol).is_some())
+                {
+                    Vec::new()
+                } else {
+                    vec![symbol]
+                }
+            }
+            None => Vec::new(),
+        }
+    }
+    /// Calls the syntax of a command and collects its outputs and warnings.
+    fn collect_outputs(&mut self, ast: &Rc<Self::S>) -> ZiaResult<Report> {
+        let mut collector = Collector::default();
+        let changes = try!(self.execute_syntax(ast, &mut collector));
+        Ok(collector.into_report(changes))
+    }
+    /// Returns the message of each warning followed by the text shown for the output, on separate lines.
+    fn describe_report(&self, report: &Report) -> String {
+        let mut lines: Vec<String> = report
+            .warnings
+            .iter()
+            .map(|w| self.get_locale().describe_warning(w))
+            .collect();
+        let output = report.output.to_string();
+        if !output.is_empty() {
+            lines.push(output);
         }
+        lines.join("\n")
     }
 }
 
 impl<S, T> Execute<T> for S
 where
     T: From<String>
+        + From<i64>
         + From<Self::C>
         + From<Self::A>
         + RemoveDefinition
@@ -122,10 +343,12 @@ where
         + SetAsDefinitionOf
         + GetDefinition
         + MaybeString
+        + MaybeInteger
+        + IsConcrete
         + GetDefinitionOf
         + GetReduction
         + FindWhatReducesToIt,
-    S: Call<T> + SyntaxConverter<T>,
+    S: Call<T> + SyntaxConverter<T> + GetLocale + GetStrictness + SuggestLabels<T>,
     S::S: Container
         + Pair<S::S>
         + Clone
@@ -153,8 +376,15 @@ impl ConceptMaker<Concept> for Context {
 /// Calling a program expressed as abstract syntax to read or write contained concepts.  
 pub trait Call<T>
 where
-    Self: Definer<T> + ExecuteReduction<T> + SyntaxReader<T>,
+    Self: Definer<T>
+        + ExecuteReduction<T>
+        + SyntaxReader<T>
+        + ConceptDeleter<T>
+        + Aliaser<T>
+        + SearchLabels<T>
+        + TrackChanges,
     T: From<String>
+        + From<i64>
         + From<Self::C>
         + From<Self::A>
         + RemoveDefinition
@@ -169,7 +399,9 @@ where
         + GetReduction
         + GetDefinition
         + GetDefinitionOf
-        + MaybeString,
+        + MaybeString
+        + MaybeInteger
+        + IsConcrete,
     Self::S: Container
         + Pair<Self::S>
         + Clone
@@ -177,84 +409,204 @@ where
         + DisplayJoint
         + PartialEq<Self::S>,
 {
-    /// If the associated concept of the syntax is a string concept that that associated string is returned. If not, the function tries to expand the abstract syntax tree. If that's possible, `call_pair` is called with the lefthand and righthand syntax parts. If not `try_expanding_then_call` is called on the tree. If a program cannot be found this way, 'try_reducing_then_call' is called on the tree.
-    fn call(&mut self, ast: &Rc<Self::S>) -> ZiaResult<String> {
+    /// If the associated concept of the syntax is a string concept that that associated string is printed to the sink. If not, the function tries to expand the abstract syntax tree. If that's possible, `call_pair` is called with the lefthand and righthand syntax parts. If not `try_expanding_then_call` is called on the tree. If a program cannot be found this way, 'try_reducing_then_call' is called on the tree.
+    fn call(&mut self, ast: &Rc<Self::S>, sink: &mut dyn Sink) -> ZiaResult<()> {
 		if let Some(c) = ast.get_concept() {
 			if let Some(s) = self.read_concept(c).get_string() {
-				return Ok(s);
+				sink.print(&s);
+				return Ok(());
 			}
 		}
         match ast.get_expansion() {
-            Some((ref left, ref right)) => self.call_pair(left, right),
-            None => self.try_expanding_then_call(ast),
+            Some((ref left, ref right)) => self.call_pair(left, right, sink),
+            None => self.try_expanding_then_call(ast, sink),
         }
     }
-    /// If the associated concept of the lefthand part of the syntax is LET then `call_as_righthand` is called with the left and right of the lefthand syntax. Tries to get the concept associated with the righthand part of the syntax. If the associated concept is `->` then the reduction of the lefthand part of the syntax is displayed. If the associated concept is `:=` the lefthand part of the syntax is expanded in its definitions and displayed. If the associated concept reduces, `call_pair` is called again with the reduced righthand syntax.
-    fn call_pair(&mut self, left: &Rc<Self::S>, right: &Rc<Self::S>) -> ZiaResult<String> {
-        if let Some(c) = left.get_concept() {
+    /// If the righthand part of the syntax is a reduction operator (`->`, `->*` or `-> n`) then the lefthand part of the syntax is reduced accordingly and called, or expressed as a value if it isn't a program, even if it is a built-in concept such as `delete`. Otherwise, if the associated concept of the lefthand part of the syntax is LET then `call_as_righthand` is called with the left and right of the lefthand syntax. If it is `delete` or `delete*` then the concept of the righthand part of the syntax is deleted, without or with its dependents. If it is `unalias` or `prefer` then the righthand part of the syntax is removed as an alias or made the label of its concept. If it is `module` then new labels belong to the module named by the righthand part of the syntax, or to no module if it is `::`, and if it is `open` then the labels of that module can be used without qualification. If it is `forget_labels` then the previous labels of the concept of the righthand part of the syntax are forgotten. If it is `search` then each label matching the glob of the righthand part of the syntax is printed separately, in alphabetical order. If it is TRACE then each step of reducing the righthand part of the syntax is printed separately. If the associated concept of the righthand part of the syntax is `previous_labels` then each previous label of the concept of the lefthand part of the syntax is printed separately, oldest first, and if it is a query such as `<-` or `used_in` then each concept found by the query is printed separately.
+    fn call_pair(
+        &mut self,
+        left: &Rc<Self::S>,
+        right: &Rc<Self::S>,
+        sink: &mut dyn Sink,
+    ) -> ZiaResult<()> {
+        let reduction = self.reduction_operator(right);
+        if let (Some(c), None) = (left.get_concept(), reduction) {
             if c == LET {
                 if let Some((ref rightleft, ref rightright)) = right.get_expansion() {
-                    return self.call_as_righthand(rightleft, rightright);
+                    return self.call_as_righthand(&self.bind_to_module(rightleft), rightright);
+                }
+            }
+            if c == DELETE || c == CASCADING_DELETE {
+                return match right.get_concept() {
+                    Some(concept) if c == DELETE => self.delete_concept(concept),
+                    Some(concept) => self.cascade_delete(concept),
+                    None => Err(ZiaError::RedundantDeletion),
+                };
+            }
+            if c == UNALIAS || c == PREFER {
+                let symbol = right.to_string();
+                return if c == UNALIAS {
+                    self.unalias(&symbol)
+                } else {
+                    self.prefer(&symbol)
+                };
+            }
+            if c == MODULE || c == OPEN {
+                if right.get_expansion().is_some() {
+                    return Err(ZiaError::NotAProgram);
+                }
+                let module = right.to_string();
+                if c == OPEN {
+                    self.open_module(&module);
+                } else if module == SEPARATOR {
+                    self.set_module(None);
+                } else {
+                    self.set_module(Some(module));
                 }
+                return Ok(());
+            }
+            if c == FORGET_LABELS {
+                return match right.get_concept().map(|r| self.forget_previous_labels(r)) {
+                    Some(ref labels) if !labels.is_empty() => Ok(()),
+                    _ => Err(ZiaError::RedundantDeletion),
+                };
+            }
+            if c == SEARCH {
+                if right.get_expansion().is_some() {
+                    return Err(ZiaError::NotAProgram);
+                }
+                print_results(
+                    sink,
+                    self.search_labels(&LabelSearch::glob(&right.to_string())),
+                );
+                return Ok(());
+            }
+            if c == TRACE {
+                let steps = try!(self.trace_reduction_of(right));
+                print_results(sink, steps.iter().map(|s| s.to_string()).collect());
+                return Ok(());
             }
         }
-        match right.get_concept() {
-            Some(c) => match c {
-                REDUCTION => {
-					if let Some((leftleft, leftright)) = left.get_expansion() {
-						if let Some(con) = leftleft.get_concept() {
-							if con == LABEL {
-								return self.reduce_label_of(&leftright);
-							}
+        if let Some(c) = right.get_concept() {
+            if c == PREVIOUS_LABELS {
+                let labels = match left.get_concept() {
+                    Some(l) => self.get_previous_labels(l),
+                    None => Vec::new(),
+                };
+                print_results(sink, labels);
+                return Ok(());
+            }
+            if let Some(found) = self.query(c, left) {
+                print_results(sink, self.display_sorted(found));
+                return Ok(());
+            }
+        }
+        match reduction {
+            Some(steps) => {
+				if let Some((leftleft, leftright)) = left.get_expansion() {
+					if let Some(con) = leftleft.get_concept() {
+						if con == LABEL {
+							let label = try!(self.reduce_label_of(&leftright));
+							sink.print(&label);
+							return Ok(());
 						}
-					};
-					let reduced_syntax = match self.reduce(left) {
-                    	None => left.clone(),
-                    	Some(rleft) => rleft,
-                	};
-					self.call(&reduced_syntax)
-				},
-                _ => Err(ZiaError::NotAProgram)
-            },
+					}
+				};
+				let reduced_syntax = try!(self.reduce_steps(left, steps));
+				match self.call(&reduced_syntax, sink) {
+					Err(ZiaError::NotAProgram) => {
+						sink.express(&reduced_syntax.to_string());
+						Ok(())
+					}
+					result => result,
+				}
+			},
             None => Err(ZiaError::NotAProgram),
         }
+    }
+    /// Returns how far the operator reduces an expression: `->` reduces by one step, `->*` reduces to the normal form and `-> n` reduces by `n` steps.
+    fn reduction_operator(&self, ast: &Rc<Self::S>) -> Option<Steps> {
+        match ast.get_concept() {
+            Some(REDUCTION) => return Some(Steps::Finite(1)),
+            Some(NORMAL_FORM) => return Some(Steps::NormalForm),
+            _ => (),
+        };
+        match ast.get_expansion() {
+            Some((ref left, ref right)) if left.get_concept() == Some(REDUCTION) => {
+                right.to_string().parse().ok().map(Steps::Finite)
+            }
+            _ => None,
+        }
     }
 	fn reduce_label_of(&self, ast: &Rc<Self::S>) -> ZiaResult<String> {
 		if let Some((left, right)) = ast.get_expansion() {
-			if let Some(concept) = right.get_concept() {
-				if concept == REDUCTION {
-					return self.reduce_label_of(&match self.reduce(&left) {
-						None => left,
-						Some(reduction) => reduction,
-					});
-				}
-				if concept == DEFINE {
-					return Ok(self.expand(&left).to_string());
-				}
+			if let Some(steps) = self.reduction_operator(&right) {
+				return self.reduce_label_of(&try!(self.reduce_steps(&left, steps)));
+			}
+			if right.get_concept() == Some(DEFINE) {
+				return self.expand(&left).map(|e| e.to_string());
 			}
 		}
 		Ok(ast.to_string())
 	}
+    /// If the operator is a query, returns the concepts it finds for the associated concept of the syntax: what reduces to it in one step (`<-`) or in any number of steps (`<-*`) and the compositions it is directly (`used_in`) or indirectly (`used_in*`) part of. Syntax without an associated concept has none of these.
+    fn query(&self, operator: usize, ast: &Rc<Self::S>) -> Option<HashSet<usize>> {
+        let concept = ast.get_concept();
+        let found = match operator {
+            REDUCES_FROM => concept.map(|c| self.read_concept(c).find_what_reduces_to_it()),
+            EVENTUALLY_REDUCES_FROM => concept.map(|c| self.find_what_its_a_normal_form_of(c)),
+            USED_IN => concept.map(|c| self.find_compositions(c)),
+            TRANSITIVELY_USED_IN => concept.map(|c| self.find_containers(c)),
+            _ => return None,
+        };
+        Some(found.unwrap_or_default())
+    }
+    /// Returns how each concept is displayed, in alphabetical order.
+    fn display_sorted(&self, concepts: HashSet<usize>) -> Vec<String> {
+        let mut displayed: Vec<String> = concepts.into_iter().map(|c| self.display(c)).collect();
+        displayed.sort();
+        displayed
+    }
     /// If the abstract syntax tree can be expanded, then `call` is called with this expansion. If not then an `Err(ZiaError::NotAProgram)` is returned
-    fn try_expanding_then_call(&mut self, ast: &Rc<Self::S>) -> ZiaResult<String> {
-        let expansion = &self.expand(ast);
+    fn try_expanding_then_call(&mut self, ast: &Rc<Self::S>, sink: &mut dyn Sink) -> ZiaResult<()> {
+        let expansion = &try!(self.expand(ast));
         if expansion != ast {
-            self.call(expansion)
+            self.call(expansion, sink)
         } else {
             Err(ZiaError::NotAProgram)
         }
     }
     /// If the abstract syntax tree can be reduced, then `call` is called with this reduction. If not then an `Err(ZiaError::NotAProgram)` is returned
-    fn try_reducing_then_call(&mut self, ast: &Rc<Self::S>) -> ZiaResult<String> {
-        let normal_form = &self.recursively_reduce(ast);
+    fn try_reducing_then_call(&mut self, ast: &Rc<Self::S>, sink: &mut dyn Sink) -> ZiaResult<()> {
+        let normal_form = &try!(self.recursively_reduce(ast));
         if normal_form != ast {
-            self.call(normal_form)
+            self.call(normal_form, sink)
         } else {
             Err(ZiaError::NotAProgram)
         }
     }
+    /// Returns the syntax of the subject of a `let` command. Within a module, an unqualified symbol refers to its label in the module even if it refers to a concept outside of the module elsewhere. Symbols of concrete concepts such as `let` and `->` keep their concepts, so they can't be shadowed, and a global symbol such as `::a` refers to its label outside of any module.
+    fn bind_to_module(&self, ast: &Rc<Self::S>) -> Rc<Self::S> {
+        let concrete = ast
+            .get_concept()
+            .is_some_and(|c| self.read_concept(c).is_concrete());
+        if ast.get_expansion().is_some() || concrete {
+            return ast.clone();
+        }
+        let symbol = ast.to_string();
+        let label = self.qualify(&symbol);
+        if label == symbol
+            || global(&symbol).is_some()
+            || (self.reads_integers() && parse_integer(&symbol).is_some())
+        {
+            ast.clone()
+        } else {
+            let concept = self.concept_from_exact_label(&label);
+            Rc::new(Self::S::from((label, concept)))
+        }
+    }
     /// If the righthand part of the syntax can be expanded, then `match_righthand_pair` is called. If not, `Err(ZiaError::NotAProgram)` is returned.
-    fn call_as_righthand(&mut self, left: &Self::S, right: &Self::S) -> ZiaResult<String> {
+    fn call_as_righthand(&mut self, left: &Self::S, right: &Self::S) -> ZiaResult<()> {
         match right.get_expansion() {
             Some((ref rightleft, ref rightright)) => {
                 self.match_righthand_pair(left, rightleft, rightright)
@@ -262,17 +614,37 @@ where
             None => Err(ZiaError::NotAProgram),
         }
     }
-    /// If the lefthand of the righthand part of the syntax is `->` then `execute_reduction` is called with the lefthand part and the righthand of the righthand part of the syntax. Similarly for `:=`, `execute_definition` is called. If the lefthand of the righthand part of the syntax is associated with a concept which isn't `->` or `:=` then if this concept reduces, `match_righthand_pair` is called with this reduced concept as an abstract syntax tree.
+    /// Makes the symbol an alias of the concept of the syntax, which is created if it doesn't exist yet. Expressions cannot be aliases and symbols that are already used cannot become aliases of other concepts.
+    fn execute_alias(&mut self, alias: &Self::S, syntax: &Self::S) -> ZiaResult<()> {
+        if alias.get_expansion().is_some() {
+            return Err(ZiaError::BadDefinition);
+        }
+        match (alias.get_concept(), syntax.get_concept()) {
+            (Some(a), Some(s)) if a == s => Err(ZiaError::RedundantAlias),
+            (Some(_), _) => Err(ZiaError::DefinitionCollision {
+                symbol: alias.to_string(),
+                expression: syntax.to_string(),
+            }),
+            (None, _) if alias.to_string() == syntax.to_string() => Err(ZiaError::RedundantAlias),
+            (None, _) => {
+                let concept = try!(self.concept_from_ast(syntax));
+                self.add_alias(&alias.to_string(), concept);
+                Ok(())
+            }
+        }
+    }
+    /// If the lefthand of the righthand part of the syntax is `->` then `execute_reduction` is called with the lefthand part and the righthand of the righthand part of the syntax. Similarly for `:=`, `execute_definition` is called, and for `alias_of`, `execute_alias` is called. If the lefthand of the righthand part of the syntax is associated with a concept which isn't `->` or `:=` then if this concept reduces, `match_righthand_pair` is called with this reduced concept as an abstract syntax tree.
     fn match_righthand_pair(
         &mut self,
         left: &Self::S,
         rightleft: &Self::S,
         rightright: &Self::S,
-    ) -> ZiaResult<String> {
+    ) -> ZiaResult<()> {
         match rightleft.get_concept() {
             Some(c) => match c {
                 REDUCTION => self.execute_reduction(left, rightright),
                 DEFINE => self.execute_definition(left, rightright),
+                ALIAS => self.execute_alias(left, rightright),
                 _ => {
                     let rightleft_reduction = self.read_concept(c).get_reduction();
                     if let Some(r) = rightleft_reduction {
@@ -290,8 +662,15 @@ where
 
 impl<S, T> Call<T> for S
 where
-    S: Definer<T> + ExecuteReduction<T> + SyntaxReader<T>,
+    S: Definer<T>
+        + ExecuteReduction<T>
+        + SyntaxReader<T>
+        + ConceptDeleter<T>
+        + Aliaser<T>
+        + SearchLabels<T>
+        + TrackChanges,
     T: From<String>
+        + From<i64>
         + From<Self::C>
         + From<Self::A>
         + RemoveDefinition
@@ -306,7 +685,9 @@ where
         + GetReduction
         + GetDefinition
         + GetDefinitionOf
-        + MaybeString,
+        + MaybeString
+        + MaybeInteger
+        + IsConcrete,
     S::S: Container
         + Pair<S::S>
         + Clone
@@ -320,6 +701,7 @@ where
 pub trait Definer<T>
 where
     T: From<String>
+        + From<i64>
         + From<Self::C>
         + From<Self::A>
         + RemoveDefinition
@@ -339,25 +721,33 @@ where
     Self::S: Pair<Self::S> + Container,
 {
     /// If the new syntax is contained within the old syntax then this returns `Err(ZiaError::InfiniteDefinition)`. Otherwise `define` is called.
-    fn execute_definition(&mut self, new: &Self::S, old: &Self::S) -> ZiaResult<String> {
+    fn execute_definition(&mut self, new: &Self::S, old: &Self::S) -> ZiaResult<()> {
         if old.contains(new) {
             Err(ZiaError::InfiniteDefinition)
         } else {
-            try!(self.define(new, old));
-            Ok("".to_string())
+            self.define(new, old)
         }
     }
-    /// If the new syntax is an expanded expression then this returns `Err(ZiaError::BadDefinition)`. Otherwise the result depends on whether the new or old syntax is associated with a concept and whether the old syntax is an expanded expression.
+    /// If the new syntax is an expanded expression then this returns `Err(ZiaError::BadDefinition)`. Otherwise the result depends on whether the new or old syntax is associated with a concept and whether the old syntax is an expanded expression. A new symbol that is only the previous label of a concept is read as unused.
     fn define(&mut self, new: &Self::S, old: &Self::S) -> ZiaResult<()> {
         if new.get_expansion().is_some() {
             Err(ZiaError::BadDefinition)
         } else {
-            match (new.get_concept(), old.get_concept(), old.get_expansion()) {
+            let symbol = new.to_string();
+            let new_concept = match new.get_concept() {
+                Some(_) if self.concept_from_label(&symbol).is_none() => {
+                    self.forget_renamed(&symbol);
+                    None
+                }
+                concept => concept,
+            };
+            match (new_concept, old.get_concept(), old.get_expansion()) {
                 (_, None, None) => Err(ZiaError::RedundantRefactor),
                 (None, Some(b), None) => self.relabel(b, &new.to_string()),
                 (None, Some(b), Some(_)) => {
                     if self.get_label(b).is_none() {
-                        self.label(b, &new.to_string())
+                        let label = self.qualify(&new.to_string());
+                        self.label(b, &label)
                     } else {
                         self.relabel(b, &new.to_string())
                     }
@@ -370,14 +760,20 @@ where
                     if a == b {
                         self.cleanly_delete_definition(a)
                     } else {
-                        Err(ZiaError::DefinitionCollision)
+                        Err(ZiaError::DefinitionCollision {
+                            symbol: new.to_string(),
+                            expression: old.to_string(),
+                        })
                     }
                 }
                 (Some(a), Some(b), Some(_)) => {
                     if a == b {
                         Err(ZiaError::RedundantDefinition)
                     } else {
-                        Err(ZiaError::DefinitionCollision)
+                        Err(ZiaError::DefinitionCollision {
+                            symbol: new.to_string(),
+                            expression: old.to_string(),
+                        })
                     }
                 }
                 (Some(a), None, Some((ref left, ref right))) => self.redefine(a, left, right),
@@ -396,10 +792,16 @@ where
             Ok(())
         }
     }
-    /// Unlabels a concept and gives it a new label.
+    /// Unlabels a concept and gives it a new label within the current module. The old label is kept as a previous label of the concept.
     fn relabel(&mut self, concept: usize, new_label: &str) -> ZiaResult<()> {
+        let label = self.qualify(new_label);
+        if let Some(old_label) = self.get_label(concept) {
+            if old_label != label {
+                self.add_previous_label(concept, &old_label);
+            }
+        }
         try!(self.unlabel(concept));
-        self.label(concept, new_label)
+        self.label(concept, &label)
     }
     /// Returns the index of a concept labelled by `syntax` and composed of concepts from `left` and `right`.
     fn define_new_syntax(
@@ -422,6 +824,7 @@ where
 impl<S, T> Definer<T> for S
 where
     T: From<String>
+        + From<i64>
         + From<Self::C>
         + From<Self::A>
         + RemoveDefinition
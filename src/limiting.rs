@@ -0,0 +1,35 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+use std::sync::{atomic::AtomicBool, Arc};
+
+/// Counting the steps taken to reduce and expand expressions.
+pub trait Limit {
+    /// Starts counting steps from zero.
+    fn reset_steps(&self);
+    /// Counts a step and returns whether evaluation may continue, i.e. the step limit hasn't been exceeded and evaluation hasn't been cancelled.
+    fn take_step(&self) -> bool;
+    /// The number of steps taken since steps were last reset.
+    fn steps_taken(&self) -> usize;
+}
+
+/// Setting how much work evaluating a command may do before it is aborted.
+pub trait SetLimits {
+    /// Sets the maximum number of steps taken to reduce and expand expressions for each command, or removes the limit if `None`.
+    fn set_step_limit(&mut self, limit: Option<usize>);
+    /// Sets a flag that aborts evaluation when set to `true`, e.g. from another thread.
+    fn set_cancellation_flag(&mut self, flag: Option<Arc<AtomicBool>>);
+}
@@ -0,0 +1,183 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+use constants::{
+    ADD, ALIAS, CASCADING_DELETE, DEFINE, DELETE, EQUALS, EVENTUALLY_REDUCES_FROM, FALSE,
+    FORGET_LABELS, GREATER, LABEL, LESS, LET, MODULE, MULTIPLY, NORMAL_FORM, OPEN, PREFER,
+    PREVIOUS_LABELS, REDUCES_FROM, REDUCTION, SEARCH, SUBTRACT, TRACE, TRANSITIVELY_USED_IN, TRUE,
+    UNALIAS, USED_IN,
+};
+use errors::{ZiaError, ZiaWarning};
+
+pub fn builtin_label(concept: usize) -> Option<&'static str> {
+    let label = match concept {
+        LABEL => "label_of",
+        DEFINE => ":=",
+        REDUCTION => "->",
+        LET => "let",
+        NORMAL_FORM => "->*",
+        TRACE => "trace",
+        ADD => "+",
+        SUBTRACT => "-",
+        MULTIPLY => "*",
+        LESS => "<",
+        GREATER => ">",
+        TRUE => "true",
+        FALSE => "false",
+        EQUALS => "=",
+        REDUCES_FROM => "<-",
+        EVENTUALLY_REDUCES_FROM => "<-*",
+        USED_IN => "used_in",
+        DELETE => "delete",
+        CASCADING_DELETE => "delete*",
+        ALIAS => "alias_of",
+        UNALIAS => "unalias",
+        PREFER => "prefer",
+        MODULE => "module",
+        OPEN => "open",
+        TRANSITIVELY_USED_IN => "used_in*",
+        PREVIOUS_LABELS => "previous_labels",
+        FORGET_LABELS => "forget_labels",
+        SEARCH => "search",
+        _ => return None,
+    };
+    Some(label)
+}
+
+pub fn describe(error: &ZiaError) -> String {
+    match *error {
+        ZiaError::RedundantReduction => "That reduction rule already exists.".to_string(),
+        ZiaError::RedundantDefinition => "That definition already exists.".to_string(),
+        ZiaError::RedundantRefactor => {
+            "Relabelling something that doesn't yet exist has no effect.".to_string()
+        }
+        ZiaError::RedundantDefinitionRemoval => {
+            "Removing a definition that doesn't exist is redundant.".to_string()
+        }
+        ZiaError::NotAProgram => "No program exists for this syntax.".to_string(),
+        ZiaError::BadDefinition => "Cannot define expressions.".to_string(),
+        ZiaError::CyclicReduction { ref chain } => format!(
+            "Cannot allow a chain of reduction rules to loop: {}.",
+            chain.join(" -> ")
+        ),
+        ZiaError::ExpandingReduction {
+            ref concept,
+            ref reduction,
+        } => format!(
+            "Cannot reduce `{}` to `{}` because its reduction would contain itself.",
+            concept, reduction
+        ),
+        ZiaError::InfiniteDefinition => {
+            "Cannot define a concept as an expression containing itself.".to_string()
+        }
+        ZiaError::EmptyParentheses => {
+            "Parentheses need to contain a symbol or expression.".to_string()
+        }
+        ZiaError::AmbiguousExpression => "Ambiguity due to lack of precedence or associativity defined for the symbols in that expression.".to_string(),
+        ZiaError::InvalidSymbol => {
+            "Symbols cannot be empty or contain whitespace or parentheses.".to_string()
+        }
+        ZiaError::DefinitionCollision {
+            ref symbol,
+            ref expression,
+        } => format!(
+            "Cannot define `{}` as `{}` because both are already used.",
+            symbol, expression
+        ),
+        ZiaError::SettingDefinitionOfConcrete => {
+            "Cannot set a definition of a concrete concept".to_string()
+        }
+        ZiaError::ConcreteReduction => "Cannot reduce a concrete concept".to_string(),
+        ZiaError::MultipleReductionPaths {
+            ref concept,
+            ref components,
+        } => format!(
+            "`{}` is already composed of concepts with their own reduction rules: `{}`.",
+            concept,
+            components.join("`, `")
+        ),
+        ZiaError::ReductionLimitExceeded {
+            steps,
+            ref expression,
+        } => format!(
+            "Evaluation was stopped after {} steps at `{}`.",
+            steps, expression
+        ),
+        ZiaError::UnboundVariable {
+            ref variable,
+            ref pattern,
+        } => format!(
+            "Cannot reduce `{}` to an expression containing `{}` because the pattern doesn't contain it.",
+            pattern, variable
+        ),
+        ZiaError::DependentConcepts {
+            ref concept,
+            ref dependents,
+        } => format!(
+            "Cannot delete `{}` while other concepts depend on it: `{}`.",
+            concept,
+            dependents.join("`, `")
+        ),
+        ZiaError::DeletingConcrete => "Cannot delete a concrete concept.".to_string(),
+        ZiaError::RedundantDeletion => {
+            "Deleting something that doesn't exist is redundant.".to_string()
+        }
+        ZiaError::RedundantAlias => "That alias already exists.".to_string(),
+        ZiaError::UnknownAlias { ref symbol } => format!("`{}` is not an alias.", symbol),
+        ZiaError::RedundantTranslation => "That label already exists in that language.".to_string(),
+        ZiaError::UnknownSymbol {
+            ref symbol,
+            ref suggestions,
+        } => {
+            if suggestions.is_empty() {
+                format!("`{}` isn't used.", symbol)
+            } else {
+                format!(
+                    "`{}` isn't used. Did you mean `{}`?",
+                    symbol,
+                    suggestions.join("`, `")
+                )
+            }
+        }
+        ZiaError::UsedLabel {
+            ref label,
+            ref concept,
+        } => format!("`{}` already refers to `{}`.", label, concept),
+        ZiaError::NormalisationCollision {
+            ref first,
+            ref second,
+        } => format!("`{}` and `{}` would become the same symbol.", first, second),
+    }
+}
+
+pub fn describe_warning(warning: &ZiaWarning) -> String {
+    match *warning {
+        ZiaWarning::ImplicitSymbol { ref symbol } => format!(
+            "`{}` wasn't used before so a new concept was made for it.",
+            symbol
+        ),
+        ZiaWarning::RenamedLabel { ref old, ref new } => {
+            format!("`{}` was renamed to `{}`.", old, new)
+        }
+        ZiaWarning::UnreachableRule {
+            ref rule,
+            ref shadowing_rule,
+        } => format!(
+            "The reduction rule of `{}` will never be used because `{}` is matched first.",
+            rule, shadowing_rule
+        ),
+    }
+}
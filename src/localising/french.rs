@@ -0,0 +1,184 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+use constants::{
+    ADD, ALIAS, CASCADING_DELETE, DEFINE, DELETE, EQUALS, EVENTUALLY_REDUCES_FROM, FALSE,
+    FORGET_LABELS, GREATER, LABEL, LESS, LET, MODULE, MULTIPLY, NORMAL_FORM, OPEN, PREFER,
+    PREVIOUS_LABELS, REDUCES_FROM, REDUCTION, SEARCH, SUBTRACT, TRACE, TRANSITIVELY_USED_IN, TRUE,
+    UNALIAS, USED_IN,
+};
+use errors::{ZiaError, ZiaWarning};
+
+pub fn builtin_label(concept: usize) -> Option<&'static str> {
+    let label = match concept {
+        LABEL => "étiquette_de",
+        DEFINE => ":=",
+        REDUCTION => "->",
+        LET => "soit",
+        NORMAL_FORM => "->*",
+        TRACE => "tracer",
+        ADD => "+",
+        SUBTRACT => "-",
+        MULTIPLY => "*",
+        LESS => "<",
+        GREATER => ">",
+        TRUE => "vrai",
+        FALSE => "faux",
+        EQUALS => "=",
+        REDUCES_FROM => "<-",
+        EVENTUALLY_REDUCES_FROM => "<-*",
+        USED_IN => "utilisé_dans",
+        DELETE => "supprimer",
+        CASCADING_DELETE => "supprimer*",
+        ALIAS => "alias_de",
+        UNALIAS => "retirer_alias",
+        PREFER => "préférer",
+        MODULE => "module",
+        OPEN => "ouvrir",
+        TRANSITIVELY_USED_IN => "utilisé_dans*",
+        PREVIOUS_LABELS => "étiquettes_précédentes",
+        FORGET_LABELS => "oublier_étiquettes",
+        SEARCH => "chercher",
+        _ => return None,
+    };
+    Some(label)
+}
+
+pub fn describe(error: &ZiaError) -> String {
+    match *error {
+        ZiaError::RedundantReduction => "Cette règle de réduction existe déjà.".to_string(),
+        ZiaError::RedundantDefinition => "Cette définition existe déjà.".to_string(),
+        ZiaError::RedundantRefactor => {
+            "Réétiqueter quelque chose qui n'existe pas encore n'a aucun effet.".to_string()
+        }
+        ZiaError::RedundantDefinitionRemoval => {
+            "Supprimer une définition qui n'existe pas est redondant.".to_string()
+        }
+        ZiaError::NotAProgram => "Aucun programme n'existe pour cette syntaxe.".to_string(),
+        ZiaError::BadDefinition => "Impossible de définir des expressions.".to_string(),
+        ZiaError::CyclicReduction { ref chain } => format!(
+            "Une chaîne de règles de réduction ne peut pas boucler : {}.",
+            chain.join(" -> ")
+        ),
+        ZiaError::ExpandingReduction {
+            ref concept,
+            ref reduction,
+        } => format!(
+            "Impossible de réduire `{}` en `{}` car sa réduction se contiendrait elle-même.",
+            concept, reduction
+        ),
+        ZiaError::InfiniteDefinition => {
+            "Impossible de définir un concept comme une expression qui le contient.".to_string()
+        }
+        ZiaError::EmptyParentheses => {
+            "Les parenthèses doivent contenir un symbole ou une expression.".to_string()
+        }
+        ZiaError::AmbiguousExpression => "Ambiguïté due à l'absence de priorité ou d'associativité définie pour les symboles de cette expression.".to_string(),
+        ZiaError::InvalidSymbol => {
+            "Les symboles ne peuvent pas être vides ni contenir d'espaces ou de parenthèses."
+                .to_string()
+        }
+        ZiaError::DefinitionCollision {
+            ref symbol,
+            ref expression,
+        } => format!(
+            "Impossible de définir `{}` comme `{}` car les deux sont déjà utilisés.",
+            symbol, expression
+        ),
+        ZiaError::SettingDefinitionOfConcrete => {
+            "Impossible de définir la composition d'un concept concret".to_string()
+        }
+        ZiaError::ConcreteReduction => "Impossible de réduire un concept concret".to_string(),
+        ZiaError::MultipleReductionPaths {
+            ref concept,
+            ref components,
+        } => format!(
+            "`{}` est déjà composé de concepts ayant leurs propres règles de réduction : `{}`.",
+            concept,
+            components.join("`, `")
+        ),
+        ZiaError::ReductionLimitExceeded {
+            steps,
+            ref expression,
+        } => format!(
+            "L'évaluation a été interrompue après {} étapes à `{}`.",
+            steps, expression
+        ),
+        ZiaError::UnboundVariable {
+            ref variable,
+            ref pattern,
+        } => format!(
+            "Impossible de réduire `{}` à une expression contenant `{}` car le motif ne le contient pas.",
+            pattern, variable
+        ),
+        ZiaError::DependentConcepts {
+            ref concept,
+            ref dependents,
+        } => format!(
+            "Impossible de supprimer `{}` tant que d'autres concepts en dépendent : `{}`.",
+            concept,
+            dependents.join("`, `")
+        ),
+        ZiaError::DeletingConcrete => "Impossible de supprimer un concept concret.".to_string(),
+        ZiaError::RedundantDeletion => {
+            "Supprimer quelque chose qui n'existe pas est redondant.".to_string()
+        }
+        ZiaError::RedundantAlias => "Cet alias existe déjà.".to_string(),
+        ZiaError::UnknownAlias { ref symbol } => format!("`{}` n'est pas un alias.", symbol),
+        ZiaError::RedundantTranslation => "Cette étiquette existe déjà dans cette langue.".to_string(),
+        ZiaError::UnknownSymbol {
+            ref symbol,
+            ref suggestions,
+        } => {
+            if suggestions.is_empty() {
+                format!("`{}` n'est pas utilisé.", symbol)
+            } else {
+                format!(
+                    "`{}` n'est pas utilisé. Vouliez-vous dire `{}` ?",
+                    symbol,
+                    suggestions.join("`, `")
+                )
+            }
+        }
+        ZiaError::UsedLabel {
+            ref label,
+            ref concept,
+        } => format!("`{}` désigne déjà `{}`.", label, concept),
+        ZiaError::NormalisationCollision {
+            ref first,
+            ref second,
+        } => format!("`{}` et `{}` deviendraient le même symbole.", first, second),
+    }
+}
+
+pub fn describe_warning(warning: &ZiaWarning) -> String {
+    match *warning {
+        ZiaWarning::ImplicitSymbol { ref symbol } => format!(
+            "`{}` n'était pas encore utilisé donc un nouveau concept a été créé pour lui.",
+            symbol
+        ),
+        ZiaWarning::RenamedLabel { ref old, ref new } => {
+            format!("`{}` a été renommé en `{}`.", old, new)
+        }
+        ZiaWarning::UnreachableRule {
+            ref rule,
+            ref shadowing_rule,
+        } => format!(
+            "La règle de réduction de `{}` ne sera jamais utilisée car `{}` correspond en premier.",
+            rule, shadowing_rule
+        ),
+    }
+}
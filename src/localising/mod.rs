@@ -0,0 +1,178 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+mod english;
+mod french;
+
+use errors::{ZiaError, ZiaResult, ZiaWarning};
+use reading::{FindWhatReducesToIt, GetDefinition};
+use translating::{is_valid_symbol, SyntaxFinder};
+
+/// The language of the labels of built-in concepts and of error messages.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    #[default]
+    English,
+    French,
+}
+
+impl Locale {
+    /// Returns the message explaining the error in this language.
+    pub fn describe(self, error: &ZiaError) -> String {
+        match self {
+            Locale::English => english::describe(error),
+            Locale::French => french::describe(error),
+        }
+    }
+    /// Returns the message explaining the warning in this language.
+    pub fn describe_warning(self, warning: &ZiaWarning) -> String {
+        match self {
+            Locale::English => english::describe_warning(warning),
+            Locale::French => french::describe_warning(warning),
+        }
+    }
+    /// Returns the label that a new context gives to a built-in concept in this language, or `None` if no built-in concept has that index.
+    pub fn builtin_label(self, concept: usize) -> Option<&'static str> {
+        match self {
+            Locale::English => english::builtin_label(concept),
+            Locale::French => french::builtin_label(concept),
+        }
+    }
+}
+
+/// Getting the language of error messages.
+pub trait GetLocale {
+    fn get_locale(&self) -> Locale;
+}
+
+/// Setting the language of error messages.
+pub trait SetLocale {
+    fn set_locale(&mut self, locale: Locale);
+}
+
+/// Storing the labels of concepts in particular languages.
+pub trait Translations
+where
+    Self: GetLocale,
+{
+    fn add_translation(&mut self, locale: Locale, concept: usize, label: &str);
+    /// Removes the label of the concept in the language and returns it.
+    fn remove_translation(&mut self, locale: Locale, concept: usize) -> Option<String>;
+    fn get_translation(&self, locale: Locale, concept: usize) -> Option<String>;
+    fn get_translated(&self, locale: Locale, label: &str) -> Option<usize>;
+    /// Returns each label in the language, as it was written, whose normalised text starts with `prefix`.
+    fn translations_starting_with(&self, locale: Locale, prefix: &str) -> Vec<String>;
+    /// The languages tried, in order, after the active language.
+    fn get_fallbacks(&self) -> Vec<Locale>;
+    fn set_fallbacks(&mut self, fallbacks: Vec<Locale>);
+    /// The active language followed by the fallback languages.
+    fn languages(&self) -> Vec<Locale> {
+        let mut languages = vec![self.get_locale()];
+        for locale in self.get_fallbacks() {
+            if !languages.contains(&locale) {
+                languages.push(locale);
+            }
+        }
+        languages
+    }
+    /// Returns the label of the concept in the first language of `languages` that it has a label in.
+    fn translated_label(&self, concept: usize) -> Option<String> {
+        self.languages()
+            .into_iter()
+            .filter_map(|locale| self.get_translation(locale, concept))
+            .next()
+    }
+    /// Returns the concept that the label refers to in the first language of `languages` that it is used in.
+    fn concept_from_translation(&self, label: &str) -> Option<usize> {
+        self.languages()
+            .into_iter()
+            .filter_map(|locale| self.get_translated(locale, label))
+            .next()
+    }
+}
+
+/// Labelling concepts in several languages and choosing the language that symbols are read and displayed in.
+pub trait Multilingual<T>
+where
+    T: FindWhatReducesToIt + GetDefinition,
+    Self: SyntaxFinder<T> + Translations + SetLocale,
+{
+    /// Labels the concept that `symbol` currently refers to with `label` in the language of `locale`, replacing its previous label in that language. The label cannot already label or be an alias of another concept.
+    fn label_in(&mut self, locale: Locale, symbol: &str, label: &str) -> ZiaResult<()> {
+        if !is_valid_symbol(label) {
+            return Err(ZiaError::InvalidSymbol);
+        }
+        let concept = match self.concept_from_label(symbol) {
+            Some(c) => c,
+            None => {
+                return Err(ZiaError::UnknownSymbol {
+                    symbol: symbol.to_string(),
+                    suggestions: Vec::new(),
+                })
+            }
+        };
+        let labelled = self
+            .get_string_concept(label)
+            .and_then(|c| self.get_labellee(c))
+            .or_else(|| self.get_aliased(label));
+        match labelled {
+            Some(c) if c != concept => {
+                return Err(ZiaError::DefinitionCollision {
+                    symbol: label.to_string(),
+                    expression: symbol.to_string(),
+                })
+            }
+            _ => (),
+        };
+        match self.get_translated(locale, label) {
+            Some(c) if c == concept => Err(ZiaError::RedundantTranslation),
+            Some(_) => Err(ZiaError::DefinitionCollision {
+                symbol: label.to_string(),
+                expression: symbol.to_string(),
+            }),
+            None => {
+                self.remove_translation(locale, concept);
+                self.add_translation(locale, concept, label);
+                Ok(())
+            }
+        }
+    }
+    /// Removes the label in the language of `locale` of the concept that `symbol` currently refers to.
+    fn unlabel_in(&mut self, locale: Locale, symbol: &str) -> ZiaResult<()> {
+        match self
+            .concept_from_label(symbol)
+            .and_then(|c| self.remove_translation(locale, c))
+        {
+            Some(_) => Ok(()),
+            None => Err(ZiaError::RedundantDeletion),
+        }
+    }
+    /// Changes the language of error messages and the language that symbols are first read and displayed in.
+    fn set_language(&mut self, locale: Locale) {
+        self.set_locale(locale);
+    }
+    /// Sets the languages tried, in order, when a symbol or concept has no label in the active language. Concepts without a label in any of these languages are read and displayed with the labels given by commands.
+    fn set_fallback_languages(&mut self, locales: Vec<Locale>) {
+        self.set_fallbacks(locales);
+    }
+}
+
+impl<S, T> Multilingual<T> for S
+where
+    T: FindWhatReducesToIt + GetDefinition,
+    S: SyntaxFinder<T> + Translations + SetLocale,
+{
+}
@@ -0,0 +1,263 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+mod tables;
+
+use self::tables::{
+    CANONICAL_DECOMPOSITIONS, COMBINING_CLASSES, COMPATIBILITY_DECOMPOSITIONS, COMPOSITIONS,
+};
+use errors::ZiaResult;
+use std::{cmp::Ordering, collections::HashMap};
+
+/// The Unicode normalisation form applied to the text of symbols before they are used as labels.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Normalisation {
+    /// Symbols are compared exactly as they are written.
+    None,
+    /// Canonically equivalent symbols (e.g. precomposed and decomposed accents) are the same label.
+    /// They look identical so by default they are treated as the same label.
+    #[default]
+    Nfc,
+    /// Compatibility equivalent symbols (e.g. full-width and ASCII letters) are also the same label.
+    Nfkc,
+}
+
+impl Normalisation {
+    /// Returns the normalised form of `string`.
+    pub fn normalise(self, string: &str) -> String {
+        match self {
+            Normalisation::None => string.to_string(),
+            Normalisation::Nfc => compose(&decompose(string, false)),
+            Normalisation::Nfkc => compose(&decompose(string, true)),
+        }
+    }
+}
+
+/// Setting how the text of symbols is normalised.
+pub trait SetNormalisation {
+    /// Returns an error, leaving the normalisation unchanged, if two different labels or aliases would be normalised to the same text.
+    fn set_normalisation(&mut self, normalisation: Normalisation) -> ZiaResult<()>;
+}
+
+/// Returns the first pair of different strings that are normalised to the same text.
+pub fn find_collision<'a, I: Iterator<Item = &'a str>>(
+    normalisation: Normalisation,
+    strings: I,
+) -> Option<(String, String)> {
+    let mut normalised: HashMap<String, &str> = HashMap::new();
+    for string in strings {
+        if let Some(other) = normalised.insert(normalisation.normalise(string), string) {
+            if other != string {
+                return Some((other.to_string(), string.to_string()));
+            }
+        }
+    }
+    None
+}
+
+/// Getting how the text of symbols is normalised.
+pub trait GetNormalisation {
+    fn get_normalisation(&self) -> Normalisation;
+    fn normalise(&self, string: &str) -> String {
+        self.get_normalisation().normalise(string)
+    }
+}
+
+const HANGUL_S_BASE: u32 = 0xAC00;
+const HANGUL_L_BASE: u32 = 0x1100;
+const HANGUL_V_BASE: u32 = 0x1161;
+const HANGUL_T_BASE: u32 = 0x11A7;
+const HANGUL_L_COUNT: u32 = 19;
+const HANGUL_V_COUNT: u32 = 21;
+const HANGUL_T_COUNT: u32 = 28;
+const HANGUL_N_COUNT: u32 = HANGUL_V_COUNT * HANGUL_T_COUNT;
+const HANGUL_S_COUNT: u32 = HANGUL_L_COUNT * HANGUL_N_COUNT;
+
+fn combining_class(c: char) -> u8 {
+    match COMBINING_CLASSES.binary_search_by(|&(start, end, _)| {
+        if end < c {
+            Ordering::Less
+        } else if start > c {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }) {
+        Ok(index) => COMBINING_CLASSES[index].2,
+        Err(_) => 0,
+    }
+}
+
+fn lookup_decomposition(
+    table: &'static [(char, &'static [char])],
+    c: char,
+) -> Option<&'static [char]> {
+    match table.binary_search_by(|&(key, _)| key.cmp(&c)) {
+        Ok(index) => Some(table[index].1),
+        Err(_) => None,
+    }
+}
+
+/// Pushes the full decomposition of `c` onto `decomposed`.
+fn decompose_char(c: char, compatibility: bool, decomposed: &mut Vec<char>) {
+    let code = c as u32;
+    if (HANGUL_S_BASE..HANGUL_S_BASE + HANGUL_S_COUNT).contains(&code) {
+        let index = code - HANGUL_S_BASE;
+        let jamos = [
+            HANGUL_L_BASE + index / HANGUL_N_COUNT,
+            HANGUL_V_BASE + (index % HANGUL_N_COUNT) / HANGUL_T_COUNT,
+            HANGUL_T_BASE + index % HANGUL_T_COUNT,
+        ];
+        for (position, jamo) in jamos.iter().enumerate() {
+            if position < 2 || *jamo != HANGUL_T_BASE {
+                decomposed.push(::std::char::from_u32(*jamo).unwrap());
+            }
+        }
+        return;
+    }
+    let mapping = match lookup_decomposition(CANONICAL_DECOMPOSITIONS, c) {
+        Some(m) => Some(m),
+        None if compatibility => lookup_decomposition(COMPATIBILITY_DECOMPOSITIONS, c),
+        None => None,
+    };
+    match mapping {
+        Some(chars) => {
+            for d in chars {
+                decompose_char(*d, compatibility, decomposed);
+            }
+        }
+        None => decomposed.push(c),
+    }
+}
+
+/// Fully decomposes a string and puts combining marks in canonical order.
+fn decompose(string: &str, compatibility: bool) -> Vec<char> {
+    let mut decomposed = Vec::new();
+    for c in string.chars() {
+        decompose_char(c, compatibility, &mut decomposed);
+    }
+    let mut start = 0;
+    while start < decomposed.len() {
+        if combining_class(decomposed[start]) == 0 {
+            start += 1;
+            continue;
+        }
+        let mut end = start;
+        while end < decomposed.len() && combining_class(decomposed[end]) != 0 {
+            end += 1;
+        }
+        decomposed[start..end].sort_by_key(|c| combining_class(*c));
+        start = end;
+    }
+    decomposed
+}
+
+fn compose_pair(first: char, second: char) -> Option<char> {
+    let (f, s) = (first as u32, second as u32);
+    if (HANGUL_L_BASE..HANGUL_L_BASE + HANGUL_L_COUNT).contains(&f)
+        && (HANGUL_V_BASE..HANGUL_V_BASE + HANGUL_V_COUNT).contains(&s)
+    {
+        let index = (f - HANGUL_L_BASE) * HANGUL_N_COUNT + (s - HANGUL_V_BASE) * HANGUL_T_COUNT;
+        return ::std::char::from_u32(HANGUL_S_BASE + index);
+    }
+    if (HANGUL_S_BASE..HANGUL_S_BASE + HANGUL_S_COUNT).contains(&f)
+        && (f - HANGUL_S_BASE).is_multiple_of(HANGUL_T_COUNT)
+        && (HANGUL_T_BASE + 1..HANGUL_T_BASE + HANGUL_T_COUNT).contains(&s)
+    {
+        return ::std::char::from_u32(f + s - HANGUL_T_BASE);
+    }
+    match COMPOSITIONS.binary_search_by(|&(a, b, _)| (a, b).cmp(&(first, second))) {
+        Ok(index) => Some(COMPOSITIONS[index].2),
+        Err(_) => None,
+    }
+}
+
+/// Canonically composes a decomposed sequence of characters.
+fn compose(decomposed: &[char]) -> String {
+    let mut composed: Vec<char> = Vec::with_capacity(decomposed.len());
+    let mut starter: Option<usize> = None;
+    let mut last_class = 0;
+    for &c in decomposed {
+        let class = combining_class(c);
+        if let Some(s) = starter {
+            let blocked = composed.len() > s + 1 && (last_class == 0 || last_class >= class);
+            if !blocked {
+                if let Some(p) = compose_pair(composed[s], c) {
+                    composed[s] = p;
+                    continue;
+                }
+            }
+        }
+        if class == 0 {
+            starter = Some(composed.len());
+        }
+        last_class = class;
+        composed.push(c);
+    }
+    composed.into_iter().collect()
+}
+
+#[cfg(test)]
+mod forms {
+    use super::Normalisation;
+    #[test]
+    fn nfc_composes_accents() {
+        assert_eq!(Normalisation::Nfc.normalise("e\u{301}"), "\u{e9}");
+    }
+    #[test]
+    fn nfc_keeps_compatibility_characters() {
+        assert_eq!(Normalisation::Nfc.normalise("\u{fb01}"), "\u{fb01}");
+    }
+    #[test]
+    fn nfkc_replaces_compatibility_characters() {
+        assert_eq!(Normalisation::Nfkc.normalise("\u{fb01}\u{ff41}"), "fia");
+    }
+    #[test]
+    fn canonical_ordering() {
+        assert_eq!(
+            Normalisation::Nfc.normalise("a\u{323}\u{302}"),
+            Normalisation::Nfc.normalise("a\u{302}\u{323}")
+        );
+    }
+    #[test]
+    fn hangul() {
+        assert_eq!(
+            Normalisation::Nfc.normalise("\u{1100}\u{1161}\u{11a8}"),
+            "\u{ac01}"
+        );
+    }
+    #[test]
+    fn unicode_samples() {
+        // Samples from NormalizationTest.txt of version 14.0.0 of the Unicode Character Database.
+        let samples = [
+            (Normalisation::Nfc, "D\u{307}", "\u{1e0a}"),
+            (Normalisation::Nfc, "D\u{307}\u{323}", "\u{1e0c}\u{307}"),
+            (Normalisation::Nfc, "\u{212b}", "\u{c5}"),
+            (Normalisation::Nfc, "\u{fb2c}", "\u{5e9}\u{5bc}\u{5c1}"),
+            (Normalisation::Nfc, "\u{1e9b}\u{323}", "\u{1e9b}\u{323}"),
+            (Normalisation::Nfkc, "\u{1e9b}\u{323}", "\u{1e69}"),
+            (Normalisation::Nfkc, "\u{2075}", "5"),
+            (Normalisation::Nfkc, "\u{ff76}\u{ff9e}", "\u{30ac}"),
+        ];
+        for &(normalisation, source, normalised) in &samples {
+            assert_eq!(normalisation.normalise(source), normalised);
+        }
+    }
+    #[test]
+    fn none_is_unchanged() {
+        assert_eq!(Normalisation::None.normalise("e\u{301}"), "e\u{301}");
+    }
+}
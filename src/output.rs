@@ -0,0 +1,175 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+use errors::ZiaWarning;
+use std::fmt;
+use std::io::{Error, Write};
+
+/// What a successfully executed command did.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Output {
+    /// The command modified the context.
+    Mutation(Changes),
+    /// The command printed text, e.g. the label of a concept.
+    Printed(String),
+    /// The command expressed the value of an expression, e.g. its reduction.
+    Value(String),
+}
+
+impl fmt::Display for Output {
+    /// Displays the text that is shown to the user of an interactive shell. Mutations show nothing.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Output::Mutation(_) => Ok(()),
+            Output::Printed(ref s) | Output::Value(ref s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// What a successfully executed command did along with the warnings it gave.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    pub output: Output,
+    pub warnings: Vec<ZiaWarning>,
+}
+
+/// The indices of the concepts that a command created, changed or removed, in ascending order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Changes {
+    pub created: Vec<usize>,
+    pub changed: Vec<usize>,
+    pub removed: Vec<usize>,
+}
+
+/// Keeping track of which concepts have been created, changed or removed.
+pub trait TrackChanges {
+    /// Returns the changes made since the last call and starts tracking changes afresh.
+    fn take_changes(&mut self) -> Changes;
+}
+
+/// Somewhere that commands write their output to, e.g. a terminal, a file or a buffer. A single command may write several outputs.
+pub trait Sink {
+    /// Receives text printed by a command, e.g. the label of a concept.
+    fn print(&mut self, text: &str);
+    /// Receives the value of an expression, e.g. its reduction. By default values are printed.
+    fn express(&mut self, value: &str) {
+        self.print(value)
+    }
+    /// Receives a warning about the command, e.g. that it used a previous label of a concept, and its message in the language of the context. By default messages are printed.
+    fn warn(&mut self, _warning: &ZiaWarning, message: &str) {
+        self.print(message)
+    }
+    /// Receives notice that a query, e.g. `<-` or `search`, found nothing. By default nothing is printed.
+    fn print_no_results(&mut self) {}
+}
+
+/// Prints each result of a query separately, or notice that there are none.
+pub fn print_results(sink: &mut dyn Sink, results: Vec<String>) {
+    if results.is_empty() {
+        sink.print_no_results();
+    }
+    for result in results {
+        sink.print(&result);
+    }
+}
+
+/// Collects each output and warning of a command.
+#[derive(Default)]
+pub struct Collector {
+    outputs: Vec<Output>,
+    warnings: Vec<ZiaWarning>,
+}
+
+impl Collector {
+    /// A command without outputs is a mutation, except for a query without results, which prints nothing. Several outputs are joined on separate lines.
+    pub fn into_report(mut self, changes: Changes) -> Report {
+        let output = if self.outputs.len() > 1 {
+            let lines: Vec<String> = self.outputs.iter().map(|o| o.to_string()).collect();
+            Output::Printed(lines.join("\n"))
+        } else {
+            self.outputs.pop().unwrap_or(Output::Mutation(changes))
+        };
+        Report {
+            output,
+            warnings: self.warnings,
+        }
+    }
+}
+
+impl Sink for Collector {
+    fn print(&mut self, text: &str) {
+        self.outputs.print(text)
+    }
+    fn express(&mut self, value: &str) {
+        self.outputs.express(value)
+    }
+    fn warn(&mut self, warning: &ZiaWarning, _message: &str) {
+        self.warnings.push(warning.clone())
+    }
+    fn print_no_results(&mut self) {
+        self.outputs.push(Output::Printed(String::new()))
+    }
+}
+
+/// Collects each output, distinguishing printed text from values.
+impl Sink for Vec<Output> {
+    fn print(&mut self, text: &str) {
+        self.push(Output::Printed(text.to_string()))
+    }
+    fn express(&mut self, value: &str) {
+        self.push(Output::Value(value.to_string()))
+    }
+}
+
+/// Collects the text of each output.
+impl Sink for Vec<String> {
+    fn print(&mut self, text: &str) {
+        self.push(text.to_string())
+    }
+}
+
+/// Writes each output on its own line to a writer such as `std::io::stdout()` or a `std::fs::File`.
+pub struct WriteSink<W: Write> {
+    writer: W,
+    error: Option<Error>,
+}
+
+impl<W: Write> WriteSink<W> {
+    pub fn new(writer: W) -> WriteSink<W> {
+        WriteSink {
+            writer,
+            error: None,
+        }
+    }
+    /// Returns the first error that occurred while writing, if any, and forgets it.
+    pub fn take_error(&mut self) -> Option<Error> {
+        self.error.take()
+    }
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> Sink for WriteSink<W> {
+    /// Output is no longer written once writing has failed.
+    fn print(&mut self, text: &str) {
+        if self.error.is_none() {
+            if let Err(e) = writeln!(self.writer, "{}", text) {
+                self.error = Some(e);
+            }
+        }
+    }
+}
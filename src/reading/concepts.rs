@@ -32,6 +32,14 @@ pub trait MaybeString {
     fn get_string(&self) -> Option<String>;
 }
 
+pub trait MaybeInteger {
+    fn get_integer(&self) -> Option<i64>;
+}
+
+pub trait IsConcrete {
+    fn is_concrete(&self) -> bool;
+}
+
 pub trait GetDefinitionOf {
     fn get_lefthand_of(&self) -> HashSet<usize>;
     fn get_righthand_of(&self) -> HashSet<usize>;
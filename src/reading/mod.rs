@@ -16,19 +16,55 @@
 */
 
 mod concepts;
+mod patterns;
 mod syntax;
 
 pub use self::concepts::*;
+pub use self::patterns::*;
 pub use self::syntax::*;
-use constants::LABEL;
-use std::{collections::HashSet, fmt, rc::Rc};
+use computing::{compute, parse_integer, Computed, Integers};
+use constants::{EQUALS, FALSE, LABEL, TRUE};
+use errors::{ZiaError, ZiaResult};
+use hosting::NativeFunctions;
+use limiting::Limit;
+use localising::Translations;
+use scoping::Unqualify;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    rc::Rc,
+};
+use tracing::{ReductionStep, Trace};
+use translating::SyntaxConverter;
+
+/// How far an expression is reduced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Steps {
+    /// Reduced by at most this many steps.
+    Finite(usize),
+    /// Reduced until no reduction rule applies.
+    NormalForm,
+}
 
 pub trait SyntaxReader<T>
 where
-    Self: GetLabel<T> + Combine<T>,
-    T: GetDefinitionOf + GetDefinition + GetReduction + MaybeString,
+    Self: GetLabel<T>
+        + Combine<T>
+        + Patterns<T>
+        + SyntaxConverter<T>
+        + NativeFunctions
+        + Trace
+        + Limit
+        + Integers
+        + Sized,
+    T: GetDefinitionOf
+        + GetDefinition
+        + GetReduction
+        + MaybeString
+        + MaybeInteger
+        + FindWhatReducesToIt,
 {
-    /// Expands syntax by definition of its associated concept.
+    /// Expands syntax by definition of its associated concept. Each definition expanded counts as a step.
     fn expand<
         U: MaybeConcept
             + MightExpand<U>
@@ -40,20 +76,21 @@ where
     >(
         &self,
         ast: &Rc<U>,
-    ) -> Rc<U> {
+    ) -> ZiaResult<Rc<U>> {
         if let Some(con) = ast.get_concept() {
             if let Some((left, right)) = self.read_concept(con).get_definition() {
-                self.combine(
-                    &self.expand(&self.to_ast::<U>(left)),
-                    &self.expand(&self.to_ast::<U>(right)),
-                )
+                try!(self.check_limit(ast));
+                Ok(self.combine(
+                    &try!(self.expand(&self.to_ast::<U>(left))),
+                    &try!(self.expand(&self.to_ast::<U>(right))),
+                ))
             } else {
-                self.to_ast::<U>(con)
+                Ok(self.to_ast::<U>(con))
             }
         } else if let Some((ref left, ref right)) = ast.get_expansion() {
-            self.combine(&self.expand(left), &self.expand(right))
+            Ok(self.combine(&try!(self.expand(left)), &try!(self.expand(right))))
         } else {
-            ast.clone()
+            Ok(ast.clone())
         }
     }
     /// Reduces the syntax as much as possible (returns the normal form syntax).
@@ -63,68 +100,429 @@ where
             + Clone
             + Pair<U>
             + MaybeConcept
-            + DisplayJoint,
+            + DisplayJoint
+            + fmt::Display,
     >(
         &self,
         ast: &Rc<U>,
-    ) -> Rc<U> {
-        match self.reduce(ast) {
-            Some(ref a) => self.recursively_reduce(a),
-            None => ast.clone(),
+    ) -> ZiaResult<Rc<U>> {
+        let mut reduced = ast.clone();
+        while let Some(r) = try!(self.reduce(&reduced)) {
+            try!(self.check_limit(&reduced));
+            reduced = r;
         }
+        Ok(reduced)
     }
-    /// Reduces the syntax by using the reduction rules of associated concepts.
+    /// Reduces the syntax by the number of steps, stopping early if the normal form is reached.
+    fn reduce_steps<
+        U: From<(String, Option<usize>)>
+            + MightExpand<U>
+            + Clone
+            + Pair<U>
+            + MaybeConcept
+            + DisplayJoint
+            + fmt::Display,
+    >(
+        &self,
+        ast: &Rc<U>,
+        steps: Steps,
+    ) -> ZiaResult<Rc<U>> {
+        match steps {
+            Steps::NormalForm => self.recursively_reduce(ast),
+            Steps::Finite(n) => {
+                let mut reduced = ast.clone();
+                for _ in 0..n {
+                    reduced = match try!(self.reduce(&reduced)) {
+                        Some(r) => {
+                            try!(self.check_limit(&reduced));
+                            r
+                        }
+                        None => break,
+                    };
+                }
+                Ok(reduced)
+            }
+        }
+    }
+    /// Counts a step of reducing or expanding the syntax. Returns an error reporting the syntax reached before the step if the step limit is exceeded or evaluation is cancelled.
+    fn check_limit<U: fmt::Display>(&self, ast: &Rc<U>) -> ZiaResult<()> {
+        if self.take_step() {
+            Ok(())
+        } else {
+            Err(ZiaError::ReductionLimitExceeded {
+                steps: self.steps_taken(),
+                expression: ast.to_string(),
+            })
+        }
+    }
+    /// Reduces the syntax by using the reduction rules of associated concepts. A concept with a native function on its own is reduced by calling it without arguments. Otherwise the syntax is reduced as a component.
     fn reduce<
         U: From<(String, Option<usize>)>
             + MightExpand<U>
             + Clone
             + Pair<U>
             + MaybeConcept
-            + DisplayJoint,
+            + DisplayJoint
+            + fmt::Display,
     >(
         &self,
         ast: &Rc<U>,
-    ) -> Option<Rc<U>> {
+    ) -> ZiaResult<Option<Rc<U>>> {
+        match ast.get_concept() {
+            Some(c) if self.get_native_function(c).is_some() => self.reduce_by_native_function(ast),
+            _ => self.reduce_component(ast),
+        }
+    }
+    /// Reduces the syntax as a component of an application, so a concept with a native function is only reduced at the head of an application. A ground reduction rule of the syntax is used first, then a native function, then a built-in operation, then a pattern rule that the syntax is an instance of, then the rules of its components.
+    fn reduce_component<
+        U: From<(String, Option<usize>)>
+            + MightExpand<U>
+            + Clone
+            + Pair<U>
+            + MaybeConcept
+            + DisplayJoint
+            + fmt::Display,
+    >(
+        &self,
+        ast: &Rc<U>,
+    ) -> ZiaResult<Option<Rc<U>>> {
         match ast.get_concept() {
             Some(c) => self.reduce_concept::<U>(c),
             None => match ast.get_expansion() {
-                None => None,
+                None => Ok(None),
                 Some((ref left, ref right)) => {
-                    self.match_left_right::<U>(self.reduce(left), self.reduce(right), left, right)
+                    match try!(self.reduce_by_function(ast)).or_else(|| self.reduce_by_pattern(ast))
+                    {
+                        Some(reduction) => Ok(Some(reduction)),
+                        None => {
+                            let left_result = try!(self.reduce_component(left));
+                            let right_result = try!(self.reduce_component(right));
+                            Ok(self.match_left_right::<U>(left_result, right_result, left, right))
+                        }
+                    }
                 }
             },
         }
     }
     /// Returns the syntax for the reduction of a concept.
     fn reduce_concept<
-        U: From<(String, Option<usize>)> + Clone + Pair<U> + MaybeConcept + DisplayJoint,
+        U: From<(String, Option<usize>)>
+            + MightExpand<U>
+            + Clone
+            + Pair<U>
+            + MaybeConcept
+            + DisplayJoint
+            + fmt::Display,
     >(
         &self,
         concept: usize,
-    ) -> Option<Rc<U>> {
+    ) -> ZiaResult<Option<Rc<U>>> {
         match self.read_concept(concept).get_reduction() {
             None => match self.read_concept(concept).get_definition() {
                 Some((left, right)) => {
-                    let left_result = self.reduce_concept::<U>(left);
-                    let right_result = self.reduce_concept::<U>(right);
-                    self.match_left_right::<U>(
-                        left_result,
-                        right_result,
-                        &self.to_ast::<U>(left),
-                        &self.to_ast::<U>(right),
-                    )
+                    let ast = self.to_ast::<U>(concept);
+                    match try!(self.reduce_by_function(&ast))
+                        .or_else(|| self.reduce_by_pattern(&ast))
+                    {
+                        Some(reduction) => Ok(Some(reduction)),
+                        None => {
+                            let left_result = try!(self.reduce_concept::<U>(left));
+                            let right_result = try!(self.reduce_concept::<U>(right));
+                            Ok(self.match_left_right::<U>(
+                                left_result,
+                                right_result,
+                                &self.to_ast::<U>(left),
+                                &self.to_ast::<U>(right),
+                            ))
+                        }
+                    }
                 }
+                None => Ok(None),
+            },
+            Some(n) => {
+                if self.is_tracing() {
+                    self.record_step(ReductionStep {
+                        before: self.display(concept),
+                        rule: self.display(concept),
+                        after: self.display(n),
+                    });
+                }
+                Ok(Some(self.to_ast::<U>(n)))
+            }
+        }
+    }
+    /// Returns the syntax for the reduction of the first pattern rule that the syntax is an instance of.
+    fn reduce_by_pattern<
+        U: From<(String, Option<usize>)>
+            + MightExpand<U>
+            + Clone
+            + Pair<U>
+            + MaybeConcept
+            + DisplayJoint
+            + fmt::Display,
+    >(
+        &self,
+        ast: &Rc<U>,
+    ) -> Option<Rc<U>> {
+        for &rule in self.get_pattern_rules() {
+            let mut bindings = HashMap::new();
+            if !self.matches_syntax(rule, ast, &mut bindings) {
+                continue;
+            }
+            if let Some(n) = self.read_concept(rule).get_reduction() {
+                let reduction = self.substitute::<U>(n, &bindings);
+                if self.is_tracing() {
+                    self.record_step(ReductionStep {
+                        before: ast.to_string(),
+                        rule: self.display(rule),
+                        after: reduction.to_string(),
+                    });
+                }
+                return Some(reduction);
+            }
+        }
+        None
+    }
+    /// Returns the syntax of the lefthand and righthand parts of the syntax, including those of the definition of its associated concept.
+    fn syntax_parts<
+        U: From<(String, Option<usize>)>
+            + MightExpand<U>
+            + Clone
+            + Pair<U>
+            + MaybeConcept
+            + DisplayJoint,
+    >(
+        &self,
+        ast: &Rc<U>,
+    ) -> Option<(Rc<U>, Rc<U>)> {
+        match ast.get_expansion() {
+            Some(parts) => Some(parts),
+            None => match ast.get_concept() {
+                Some(c) => self
+                    .read_concept(c)
+                    .get_definition()
+                    .map(|(left, right)| (self.to_ast::<U>(left), self.to_ast::<U>(right))),
                 None => None,
             },
-            Some(n) => Some(self.to_ast::<U>(n)),
         }
     }
+    /// Returns the syntax for the reduction computed by the native function of the concept on the left of the syntax, if it has one. The concepts it's applied to are passed to the native function, which isn't called if any of them isn't associated with a concept. Returns the error returned by the native function, if any.
+    fn reduce_by_native_function<
+        U: From<(String, Option<usize>)>
+            + MightExpand<U>
+            + Clone
+            + Pair<U>
+            + MaybeConcept
+            + DisplayJoint
+            + fmt::Display,
+    >(
+        &self,
+        ast: &Rc<U>,
+    ) -> ZiaResult<Option<Rc<U>>> {
+        let mut head = ast.clone();
+        let mut arguments = Vec::new();
+        let function = loop {
+            if let Some(c) = head.get_concept() {
+                if let Some(function) = self.get_native_function(c) {
+                    break function;
+                }
+            }
+            head = match self.syntax_parts(&head) {
+                Some((left, right)) => match right.get_concept() {
+                    Some(argument) => {
+                        arguments.push(argument);
+                        left
+                    }
+                    None => return Ok(None),
+                },
+                None => return Ok(None),
+            };
+        };
+        arguments.reverse();
+        match try!(function(self, &arguments)) {
+            Some(ref expr) => {
+                let reduction = try!(self.ast_from_expr::<U>(expr));
+                if self.is_tracing() {
+                    self.record_step(ReductionStep {
+                        before: ast.to_string(),
+                        rule: head.to_string(),
+                        after: reduction.to_string(),
+                    });
+                }
+                Ok(Some(reduction))
+            }
+            None => Ok(None),
+        }
+    }
+    /// Returns the integer value of the syntax if it is associated with an integer concept or is an integer literal and integers are read.
+    fn integer_of<U: MaybeConcept + MightExpand<U> + fmt::Display>(
+        &self,
+        ast: &Rc<U>,
+    ) -> Option<i64> {
+        match ast.get_concept() {
+            Some(c) => self.read_concept(c).get_integer(),
+            None if ast.get_expansion().is_none() && self.reads_integers() => {
+                parse_integer(&ast.to_string())
+            }
+            None => None,
+        }
+    }
+    /// Returns the syntax for the result of a native function or, failing that, a built-in operation.
+    fn reduce_by_function<
+        U: From<(String, Option<usize>)>
+            + MightExpand<U>
+            + Clone
+            + Pair<U>
+            + MaybeConcept
+            + DisplayJoint
+            + fmt::Display,
+    >(
+        &self,
+        ast: &Rc<U>,
+    ) -> ZiaResult<Option<Rc<U>>> {
+        match try!(self.reduce_by_native_function(ast)) {
+            Some(reduction) => Ok(Some(reduction)),
+            None => self.reduce_natively(ast),
+        }
+    }
+    /// Returns the syntax for the result of a built-in operation if the syntax is of the form `left (operator right)`. The operands of `=` can be any syntax, whose normal forms are compared, whereas the operands of other operators must be integers. Returns an error if reducing the operands of `=` to their normal forms fails.
+    fn reduce_natively<
+        U: From<(String, Option<usize>)>
+            + MightExpand<U>
+            + Clone
+            + Pair<U>
+            + MaybeConcept
+            + DisplayJoint
+            + fmt::Display,
+    >(
+        &self,
+        ast: &Rc<U>,
+    ) -> ZiaResult<Option<Rc<U>>> {
+        let (left, right) = match self.syntax_parts(ast) {
+            Some(parts) => parts,
+            None => return Ok(None),
+        };
+        let (operator, operand) = match self.syntax_parts(&right) {
+            Some(parts) => parts,
+            None => return Ok(None),
+        };
+        let computed = match (
+            operator.get_concept(),
+            self.integer_of(&left),
+            self.integer_of(&operand),
+        ) {
+            (Some(EQUALS), _, _) => {
+                let l = try!(self.recursively_reduce(&left));
+                let r = try!(self.recursively_reduce(&operand));
+                Computed::Concept(if self.same_syntax(&l, &r) {
+                    TRUE
+                } else {
+                    FALSE
+                })
+            }
+            (Some(o), Some(l), Some(r)) => match compute(o, l, r) {
+                Some(computed) => computed,
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        let reduction = match computed {
+            Computed::Integer(n) => Rc::new(self.ast_from_symbol::<U>(&n.to_string())),
+            Computed::Concept(c) => self.to_ast::<U>(c),
+        };
+        if self.is_tracing() {
+            self.record_step(ReductionStep {
+                before: ast.to_string(),
+                rule: operator.to_string(),
+                after: reduction.to_string(),
+            });
+        }
+        Ok(Some(reduction))
+    }
+    /// Whether two syntax trees express the same concept, or the same expression if either isn't associated with a concept.
+    fn same_syntax<U: MaybeConcept + fmt::Display>(&self, ast: &Rc<U>, other: &Rc<U>) -> bool {
+        match (ast.get_concept(), other.get_concept()) {
+            (Some(a), Some(b)) => a == b,
+            _ => ast.to_string() == other.to_string(),
+        }
+    }
+    /// Whether the syntax is an instance of `pattern`, binding the pattern variables consistently.
+    fn matches_syntax<
+        U: From<(String, Option<usize>)>
+            + MightExpand<U>
+            + Clone
+            + Pair<U>
+            + MaybeConcept
+            + DisplayJoint,
+    >(
+        &self,
+        pattern: usize,
+        ast: &Rc<U>,
+        bindings: &mut HashMap<usize, Rc<U>>,
+    ) -> bool {
+        if self.is_variable(pattern) {
+            if let Some(bound) = bindings.get(&pattern) {
+                return bound.display_joint() == ast.display_joint();
+            }
+            bindings.insert(pattern, ast.clone());
+            return true;
+        }
+        if ast.get_concept() == Some(pattern) {
+            return true;
+        }
+        match (
+            self.read_concept(pattern).get_definition(),
+            self.syntax_parts(ast),
+        ) {
+            (Some((pl, pr)), Some((ref left, ref right))) => {
+                self.matches_syntax(pl, left, bindings) && self.matches_syntax(pr, right, bindings)
+            }
+            _ => false,
+        }
+    }
+    /// Returns the syntax for a concept with its pattern variables replaced by the syntax bound to them.
+    fn substitute<
+        U: From<(String, Option<usize>)> + Clone + Pair<U> + MaybeConcept + DisplayJoint,
+    >(
+        &self,
+        concept: usize,
+        bindings: &HashMap<usize, Rc<U>>,
+    ) -> Rc<U> {
+        if let Some(bound) = bindings.get(&concept) {
+            return bound.clone();
+        }
+        match self.read_concept(concept).get_definition() {
+            Some((left, right)) if self.contains_variable(concept) => self.contract_pair::<U>(
+                &self.substitute(left, bindings),
+                &self.substitute(right, bindings),
+            ),
+            _ => self.to_ast::<U>(concept),
+        }
+    }
+    /// Reduces the syntax to its normal form and returns each rewrite made by a reduction rule.
+    fn trace_reduction_of<
+        U: From<(String, Option<usize>)>
+            + MightExpand<U>
+            + Clone
+            + Pair<U>
+            + MaybeConcept
+            + DisplayJoint
+            + fmt::Display,
+    >(
+        &self,
+        ast: &Rc<U>,
+    ) -> ZiaResult<Vec<ReductionStep>> {
+        self.start_tracing();
+        let result = self.recursively_reduce(ast);
+        let steps = self.stop_tracing();
+        result.map(|_| steps)
+    }
     /// Returns the syntax for a concept.
     fn to_ast<U: From<(String, Option<usize>)> + Clone + Pair<U> + MaybeConcept + DisplayJoint>(
         &self,
         concept: usize,
     ) -> Rc<U> {
-        match self.get_label(concept) {
+        match self.get_display_label(concept) {
             Some(s) => Rc::new(U::from((s, Some(concept)))),
             None => match self.read_concept(concept).get_definition() {
                 Some((left, right)) => {
@@ -162,7 +560,7 @@ where
                 let maydef = self.find_definition(lc, rc);
                 (
                     match maydef {
-                        Some(def) => match self.get_label(def) {
+                        Some(def) => match self.get_display_label(def) {
                             Some(a) => a,
                             None => lefthand.display_joint() + " " + &righthand.display_joint(),
                         },
@@ -182,8 +580,20 @@ where
 
 impl<S, T> SyntaxReader<T> for S
 where
-    S: GetLabel<T> + Combine<T>,
-    T: GetDefinitionOf + GetDefinition + MaybeString + GetReduction,
+    S: GetLabel<T>
+        + Combine<T>
+        + Patterns<T>
+        + SyntaxConverter<T>
+        + NativeFunctions
+        + Trace
+        + Limit
+        + Integers,
+    T: GetDefinitionOf
+        + GetDefinition
+        + MaybeString
+        + MaybeInteger
+        + GetReduction
+        + FindWhatReducesToIt,
 {
 }
 pub trait Display<T>
@@ -192,23 +602,34 @@ where
     T: MaybeString + GetDefinitionOf + GetDefinition + GetReduction,
 {
     fn display(&self, concept: usize) -> String {
+        self.try_display(concept)
+            .expect("Unlabelled concept with no definition!")
+    }
+    /// Returns how a concept is displayed, or `None` if the concept doesn't exist or it or one of its components is unlabelled and has no definition.
+    fn try_display(&self, concept: usize) -> Option<String> {
+        if !self.has_concept(concept) {
+            return None;
+        }
         match self.read_concept(concept).get_string() {
-            Some(s) => "\"".to_string() + &s + "\"",
-            None => match self.get_label(concept) {
-                Some(l) => l,
+            Some(s) => Some("\"".to_string() + &s + "\""),
+            None => match self.get_display_label(concept) {
+                Some(l) => Some(l),
                 None => match self.read_concept(concept).get_definition() {
                     Some((left, right)) => {
-                        let mut left_string = self.display(left);
-                        if left_string.contains(' ') {
-                            left_string = "(".to_string() + &left_string;
-                        }
-                        let mut right_string = self.display(right);
-                        if right_string.contains(' ') {
-                            right_string += ")";
-                        }
-                        left_string + " " + &right_string
+                        let parenthesise = |s: String| {
+                            if s.contains(' ') {
+                                "(".to_string() + &s + ")"
+                            } else {
+                                s
+                            }
+                        };
+                        self.try_display(left).and_then(|left_string| {
+                            self.try_display(right).map(|right_string| {
+                                parenthesise(left_string) + " " + &parenthesise(right_string)
+                            })
+                        })
                     }
-                    None => panic!("Unlabelled concept with no definition!"),
+                    None => None,
                 },
             },
         }
@@ -224,7 +645,7 @@ where
 pub trait GetLabel<T>
 where
     T: MaybeString + GetDefinitionOf + GetDefinition + GetReduction,
-    Self: GetNormalForm<T> + GetConceptOfLabel<T>,
+    Self: GetNormalForm<T> + GetConceptOfLabel<T> + Unqualify + Translations,
 {
     fn get_label(&self, concept: usize) -> Option<String> {
         match self.get_concept_of_label(concept) {
@@ -235,12 +656,17 @@ where
             },
         }
     }
+    /// Returns the label of the concept as it is displayed, i.e. its label in the active or a fallback language or, failing that, its label without its module if that is unambiguous.
+    fn get_display_label(&self, concept: usize) -> Option<String> {
+        self.translated_label(concept)
+            .or_else(|| self.get_label(concept).map(|label| self.unqualify(label)))
+    }
 }
 
 impl<S, T> GetLabel<T> for S
 where
     T: MaybeString + GetDefinitionOf + GetDefinition + GetReduction,
-    S: GetNormalForm<T> + GetConceptOfLabel<T>,
+    S: GetNormalForm<T> + GetConceptOfLabel<T> + Unqualify + Translations,
 {
 }
 pub trait Combine<T>
@@ -321,6 +747,14 @@ where
             },
         }
     }
+    /// Returns the concept followed by each concept in its chain of reduction rules, ending with its normal form.
+    fn get_reduction_chain(&self, concept: usize) -> Vec<usize> {
+        let mut chain = vec![concept];
+        while let Some(n) = self.read_concept(chain[chain.len() - 1]).get_reduction() {
+            chain.push(n);
+        }
+        chain
+    }
 }
 
 impl<S, T> GetNormalForm<T> for S
@@ -442,6 +876,43 @@ where
 {
 }
 
+pub trait FindCompositions<T>
+where
+    T: GetDefinition + GetDefinitionOf,
+    Self: ConceptReader<T>,
+{
+    /// Returns the compositions whose lefthand or righthand part is the concept, except the compositions that label concepts.
+    fn find_compositions(&self, concept: usize) -> HashSet<usize> {
+        let read_concept = self.read_concept(concept);
+        read_concept
+            .get_lefthand_of()
+            .union(&read_concept.get_righthand_of())
+            .filter(|c| !matches!(self.read_concept(**c).get_definition(), Some((LABEL, _))))
+            .cloned()
+            .collect()
+    }
+    /// Returns the compositions that contain the concept however deeply it is nested.
+    fn find_containers(&self, concept: usize) -> HashSet<usize> {
+        let mut containers = HashSet::new();
+        let mut stack = vec![concept];
+        while let Some(inner) = stack.pop() {
+            for composition in self.find_compositions(inner) {
+                if containers.insert(composition) {
+                    stack.push(composition);
+                }
+            }
+        }
+        containers
+    }
+}
+
+impl<S, T> FindCompositions<T> for S
+where
+    S: ConceptReader<T>,
+    T: GetDefinition + GetDefinitionOf,
+{
+}
+
 pub trait Container<T>
 where
     Self: ConceptReader<T>,
@@ -468,4 +939,6 @@ where
 
 pub trait ConceptReader<T> {
     fn read_concept(&self, usize) -> &T;
+    /// Whether a concept with this index exists.
+    fn has_concept(&self, id: usize) -> bool;
 }
@@ -0,0 +1,279 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+use super::{
+    Display, FindDefinition, GetDefinition, GetDefinitionOf, GetLabel, GetReduction, MaybeString,
+};
+use errors::{ZiaError, ZiaResult};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    rc::Rc,
+};
+
+/// An expression of concepts whose compositions may not be concepts themselves, such as the
+/// reduction of a pattern rule with its pattern variables replaced.
+#[derive(Debug, PartialEq)]
+pub enum Term {
+    Concept(usize),
+    Pair(Rc<Term>, Rc<Term>),
+}
+
+/// Storing the concepts containing pattern variables that have reduction rules, in the order they
+/// were given a reduction rule.
+pub trait PatternRules {
+    /// Returns the pattern rules in the order they are tried in.
+    fn get_pattern_rules(&self) -> &[usize];
+    /// Adds the concept as the last pattern rule unless it is already a pattern rule.
+    fn add_pattern_rule(&mut self, concept: usize);
+    fn remove_pattern_rule(&mut self, concept: usize);
+}
+
+/// Reading reduction rules whose lefthand side contains pattern variables. A pattern variable is a
+/// concept labelled with a symbol starting with `_`, e.g. `_x`, and can match any expression.
+pub trait Patterns<T>
+where
+    T: MaybeString + GetDefinitionOf + GetDefinition + GetReduction,
+    Self: GetLabel<T> + FindDefinition<T> + PatternRules + Sized,
+{
+    fn is_variable(&self, concept: usize) -> bool {
+        match self.get_label(concept) {
+            Some(label) => is_variable_symbol(&label),
+            None => false,
+        }
+    }
+    fn contains_variable(&self, concept: usize) -> bool {
+        self.is_variable(concept)
+            || match self.read_concept(concept).get_definition() {
+                Some((left, right)) => {
+                    self.contains_variable(left) || self.contains_variable(right)
+                }
+                None => false,
+            }
+    }
+    /// Returns the pattern variables that a concept is composed of.
+    fn find_variables(&self, concept: usize) -> BTreeSet<usize> {
+        let mut variables = BTreeSet::new();
+        if self.is_variable(concept) {
+            variables.insert(concept);
+        } else if let Some((left, right)) = self.read_concept(concept).get_definition() {
+            variables.extend(self.find_variables(left));
+            variables.extend(self.find_variables(right));
+        }
+        variables
+    }
+    /// Whether `concept` is an instance of `pattern`, binding the pattern variables consistently.
+    fn matches_concept(
+        &self,
+        pattern: usize,
+        concept: usize,
+        bindings: &mut HashMap<usize, usize>,
+    ) -> bool {
+        if self.is_variable(pattern) {
+            if let Some(bound) = bindings.get(&pattern) {
+                return *bound == concept;
+            }
+            bindings.insert(pattern, concept);
+            return true;
+        }
+        if pattern == concept {
+            return true;
+        }
+        match (
+            self.read_concept(pattern).get_definition(),
+            self.read_concept(concept).get_definition(),
+        ) {
+            (Some((pl, pr)), Some((l, r))) => {
+                self.matches_concept(pl, l, bindings) && self.matches_concept(pr, r, bindings)
+            }
+            _ => false,
+        }
+    }
+    /// Returns each pair of pattern rules, one of which is `rule`, where the first rule is never used because every instance of it is an instance of the second rule, which was defined earlier and so is tried first.
+    fn find_unreachable_rules(&self, rule: usize) -> Vec<(usize, usize)> {
+        let rules = self.get_pattern_rules();
+        let position = match rules.iter().position(|&r| r == rule) {
+            Some(p) => p,
+            None => return Vec::new(),
+        };
+        let mut unreachable = Vec::new();
+        for (index, &other) in rules.iter().enumerate() {
+            if other == rule {
+                continue;
+            }
+            let (earlier, later) = if index < position {
+                (other, rule)
+            } else {
+                (rule, other)
+            };
+            if self.matches_concept(earlier, later, &mut HashMap::new()) {
+                unreachable.push((later, earlier));
+            }
+        }
+        unreachable
+    }
+    /// Returns the expression of a concept, expanding its definitions and replacing the pattern variables bound by `bindings`.
+    fn term_of(&self, concept: usize, bindings: &HashMap<usize, Rc<Term>>) -> Rc<Term> {
+        if let Some(bound) = bindings.get(&concept) {
+            return bound.clone();
+        }
+        match self.read_concept(concept).get_definition() {
+            Some((left, right)) => Rc::new(Term::Pair(
+                self.term_of(left, bindings),
+                self.term_of(right, bindings),
+            )),
+            None => Rc::new(Term::Concept(concept)),
+        }
+    }
+    /// Whether the expression is an instance of `pattern`, binding the pattern variables consistently.
+    fn matches_term(
+        &self,
+        pattern: usize,
+        term: &Rc<Term>,
+        bindings: &mut HashMap<usize, Rc<Term>>,
+    ) -> bool {
+        if self.is_variable(pattern) {
+            if let Some(bound) = bindings.get(&pattern) {
+                return bound == term;
+            }
+            bindings.insert(pattern, term.clone());
+            return true;
+        }
+        match **term {
+            Term::Pair(ref left, ref right) => match self.read_concept(pattern).get_definition() {
+                Some((pl, pr)) => {
+                    self.matches_term(pl, left, bindings) && self.matches_term(pr, right, bindings)
+                }
+                None => false,
+            },
+            Term::Concept(c) => pattern == c,
+        }
+    }
+    /// Whether the expression or any of its components is an instance of `pattern`.
+    fn contains_instance(&self, pattern: usize, term: &Rc<Term>) -> bool {
+        self.matches_term(pattern, term, &mut HashMap::new())
+            || match **term {
+                Term::Pair(ref left, ref right) => {
+                    self.contains_instance(pattern, left) || self.contains_instance(pattern, right)
+                }
+                Term::Concept(_) => false,
+            }
+    }
+    /// Returns the concept of an expression if it has one.
+    fn concept_of_term(&self, term: &Term) -> Option<usize> {
+        match *term {
+            Term::Concept(c) => Some(c),
+            Term::Pair(ref left, ref right) => {
+                match (self.concept_of_term(left), self.concept_of_term(right)) {
+                    (Some(l), Some(r)) => self.find_definition(l, r),
+                    _ => None,
+                }
+            }
+        }
+    }
+    /// Returns each expression that the expression can be rewritten to in one step by a pattern rule or by the reduction rule of a concept without pattern variables, together with the concept of the rule.
+    fn rewrites(&self, term: &Rc<Term>) -> Vec<(usize, Rc<Term>)> {
+        let mut rewritten = Vec::new();
+        for &rule in self.get_pattern_rules() {
+            let mut bindings = HashMap::new();
+            if self.matches_term(rule, term, &mut bindings) {
+                if let Some(reduction) = self.read_concept(rule).get_reduction() {
+                    rewritten.push((rule, self.term_of(reduction, &bindings)));
+                }
+            }
+        }
+        if let Some(concept) = self.concept_of_term(term) {
+            if !self.get_pattern_rules().contains(&concept) {
+                if let Some(reduction) = self.read_concept(concept).get_reduction() {
+                    rewritten.push((concept, self.term_of(reduction, &HashMap::new())));
+                }
+            }
+        }
+        if let Term::Pair(ref left, ref right) = **term {
+            for (rule, l) in self.rewrites(left) {
+                rewritten.push((rule, Rc::new(Term::Pair(l, right.clone()))));
+            }
+            for (rule, r) in self.rewrites(right) {
+                rewritten.push((rule, Rc::new(Term::Pair(left.clone(), r))));
+            }
+        }
+        rewritten
+    }
+    /// Checks that a pattern rule binds every variable of its reduction and that the reduction of any reduction rule doesn't contain an instance of the lefthand side of the rule or start a chain of other rules leading back to it, either of which would make reductions never terminate. Rules without pattern variables are checked too, as they may lead back to themselves through pattern rules.
+    fn check_reduction_rule(&self, pattern: usize, reduction: usize) -> ZiaResult<()> {
+        if self.contains_variable(pattern) {
+            let bound_variables = self.find_variables(pattern);
+            for variable in self.find_variables(reduction) {
+                if !bound_variables.contains(&variable) {
+                    return Err(ZiaError::UnboundVariable {
+                        variable: self.display(variable),
+                        pattern: self.display(pattern),
+                    });
+                }
+            }
+        }
+        let reduction_term = self.term_of(reduction, &HashMap::new());
+        if self.contains_instance(pattern, &reduction_term) {
+            return Err(ZiaError::ExpandingReduction {
+                concept: self.display(pattern),
+                reduction: self.display(reduction),
+            });
+        }
+        let mut chain = vec![pattern];
+        if self.leads_back_to(pattern, &reduction_term, &mut chain, &mut HashSet::new()) {
+            Err(ZiaError::CyclicReduction {
+                chain: chain.iter().map(|c| self.display(*c)).collect(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+    /// Whether the expression contains an instance of `pattern`, either directly or after being rewritten by other rules. If so, the concepts of those rules and then `pattern` are added to `chain`.
+    fn leads_back_to(
+        &self,
+        pattern: usize,
+        term: &Rc<Term>,
+        chain: &mut Vec<usize>,
+        visited: &mut HashSet<usize>,
+    ) -> bool {
+        if self.contains_instance(pattern, term) {
+            chain.push(pattern);
+            return true;
+        }
+        for (rule, next) in self.rewrites(term) {
+            if rule == pattern || !visited.insert(rule) {
+                continue;
+            }
+            chain.push(rule);
+            if self.leads_back_to(pattern, &next, chain, visited) {
+                return true;
+            }
+            chain.pop();
+        }
+        false
+    }
+}
+
+impl<S, T> Patterns<T> for S
+where
+    T: MaybeString + GetDefinitionOf + GetDefinition + GetReduction,
+    S: GetLabel<T> + FindDefinition<T> + PatternRules,
+{
+}
+
+/// Whether a symbol is a pattern variable.
+pub fn is_variable_symbol(symbol: &str) -> bool {
+    symbol.starts_with('_') && symbol.chars().count() > 1
+}
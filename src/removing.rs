@@ -15,8 +15,12 @@
     along with this program. If not, see <http://www.gnu.org/licenses/>.
 */
 
+use constants::LABEL;
 use errors::{ZiaError, ZiaResult};
-use reading::{FindWhatReducesToIt, MaybeDisconnected, MaybeString};
+use reading::{
+    Display, FindCompositions, FindWhatReducesToIt, IsConcrete, MaybeDisconnected, MaybeString,
+};
+use std::collections::BTreeSet;
 use writing::{
     ConceptReader, DeleteDefinition, GetDefinition, GetDefinitionOf, GetReduction,
     NoLongerReducesFrom, RemoveAsDefinitionOf, RemoveDefinition, RemoveReduction, Unlabeller,
@@ -70,6 +74,125 @@ where
 {
 }
 
+/// Deleting concepts on request, either refusing while other concepts depend on them or deleting those dependents too.
+pub trait ConceptDeleter<T>
+where
+    Self: DefinitionDeleter<T> + FindCompositions<T> + Display<T>,
+    T: RemoveDefinition
+        + RemoveAsDefinitionOf
+        + RemoveReduction
+        + NoLongerReducesFrom
+        + GetDefinitionOf
+        + GetDefinition
+        + FindWhatReducesToIt
+        + GetReduction
+        + MaybeString
+        + IsConcrete,
+{
+    /// Returns the compositions of the concept and the concepts that reduce to it.
+    fn find_dependents(&self, concept: usize) -> BTreeSet<usize> {
+        let mut dependents: BTreeSet<usize> = self.find_compositions(concept).into_iter().collect();
+        dependents.extend(self.read_concept(concept).find_what_reduces_to_it());
+        dependents
+    }
+    /// Deletes the concept unless it is concrete or other concepts depend on it, in which case the dependents are listed in the error.
+    fn delete_concept(&mut self, concept: usize) -> ZiaResult<()> {
+        if self.read_concept(concept).is_concrete() {
+            return Err(ZiaError::DeletingConcrete);
+        }
+        let dependents = self.find_dependents(concept);
+        if dependents.is_empty() {
+            self.cascade_delete(concept)
+        } else {
+            let mut dependents: Vec<String> = dependents.iter().map(|d| self.display(*d)).collect();
+            dependents.sort();
+            Err(ZiaError::DependentConcepts {
+                concept: self.display(concept),
+                dependents,
+            })
+        }
+    }
+    /// Deletes the concept and every concept that depends on it. Afterwards, the concepts that they were composed of or reduced to are also deleted if they are no longer used.
+    fn cascade_delete(&mut self, concept: usize) -> ZiaResult<()> {
+        if self.read_concept(concept).is_concrete() {
+            return Err(ZiaError::DeletingConcrete);
+        }
+        let mut deleted = BTreeSet::new();
+        let mut neighbours = BTreeSet::new();
+        try!(self.delete_with_dependents(concept, &mut deleted, &mut neighbours));
+        while let Some(neighbour) = neighbours.iter().next().cloned() {
+            neighbours.remove(&neighbour);
+            if !deleted.contains(&neighbour) && self.is_unused(neighbour) {
+                try!(self.delete_with_dependents(neighbour, &mut deleted, &mut neighbours));
+            }
+        }
+        Ok(())
+    }
+    /// Whether the concept is abstract, nothing depends on it, it has no reduction rule and it is either a labelled concept without a definition or an unlabelled composition.
+    fn is_unused(&self, concept: usize) -> bool {
+        let read_concept = self.read_concept(concept);
+        !read_concept.is_concrete()
+            && read_concept.get_reduction().is_none()
+            && self.find_dependents(concept).is_empty()
+            && (self.get_concept_of_label(concept).is_some()
+                != read_concept.get_definition().is_some())
+    }
+    fn delete_with_dependents(
+        &mut self,
+        concept: usize,
+        deleted: &mut BTreeSet<usize>,
+        neighbours: &mut BTreeSet<usize>,
+    ) -> ZiaResult<()> {
+        while let Some(dependent) = self.find_dependents(concept).into_iter().next() {
+            try!(self.delete_with_dependents(dependent, deleted, neighbours));
+        }
+        if let Some(reduction) = self.read_concept(concept).get_reduction() {
+            try!(self.delete_reduction(concept));
+            neighbours.insert(reduction);
+        }
+        if let Some((left, right)) = self.read_concept(concept).get_definition() {
+            self.delete_definition(concept, left, right);
+            neighbours.insert(left);
+            neighbours.insert(right);
+        }
+        try!(self.delete_label(concept));
+        self.remove_concept(concept);
+        deleted.insert(concept);
+        Ok(())
+    }
+    /// Removes the label of the concept, if it has one, along with the composition that labels it and the string concept of the label.
+    fn delete_label(&mut self, concept: usize) -> ZiaResult<()> {
+        if let Some(label) = self.get_concept_of_label(concept) {
+            let string = self.read_concept(label).get_reduction();
+            try!(self.unlabel(concept));
+            self.delete_definition(label, LABEL, concept);
+            self.remove_concept(label);
+            if let Some(s) = string {
+                if self.read_concept(s).find_what_reduces_to_it().is_empty() {
+                    self.remove_concept(s);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<S, T> ConceptDeleter<T> for S
+where
+    S: DefinitionDeleter<T> + FindCompositions<T> + Display<T>,
+    T: RemoveDefinition
+        + RemoveAsDefinitionOf
+        + RemoveReduction
+        + NoLongerReducesFrom
+        + GetDefinitionOf
+        + GetDefinition
+        + FindWhatReducesToIt
+        + GetReduction
+        + MaybeString
+        + IsConcrete,
+{
+}
+
 pub trait ConceptRemover<T>
 where
     Self: BlindConceptRemover + ConceptReader<T> + StringRemover,
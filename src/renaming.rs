@@ -0,0 +1,36 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+
+/// Storing the labels that concepts had before they were relabelled so that commands written with
+/// the previous labels can still be read.
+pub trait LabelHistory {
+    fn add_previous_label(&mut self, concept: usize, label: &str);
+    /// Returns the previous labels of the concept, oldest first.
+    fn get_previous_labels(&self, concept: usize) -> Vec<String>;
+    /// Removes the previous labels of the concept and returns them.
+    fn forget_previous_labels(&mut self, concept: usize) -> Vec<String>;
+    /// Returns the concept that was most recently labelled by `label` before being relabelled.
+    fn get_renamed(&self, label: &str) -> Option<usize>;
+    /// Stops reading `label` as a previous label, without warning that it was read in the current command.
+    fn forget_renamed(&mut self, label: &str);
+    /// Whether symbols that aren't used can be read as the previous label of a concept.
+    fn resolves_previous_labels(&self) -> bool;
+    fn set_resolving_previous_labels(&mut self, resolving: bool);
+    /// Keeps a previous label that was read so that a warning can be given once the command is executed.
+    fn record_renamed_use(&self, label: &str, concept: usize);
+    fn take_renamed_uses(&self) -> Vec<(String, usize)>;
+}
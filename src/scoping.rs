@@ -0,0 +1,90 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+use reading::is_variable_symbol;
+
+/// Separates the name of a module from the symbol it qualifies, e.g. `logic::not`.
+pub const SEPARATOR: &str = "::";
+
+/// Keeping track of the module that new labels belong to and of the modules whose labels can be used without qualification.
+pub trait Modules {
+    fn get_module(&self) -> Option<String>;
+    /// Changes the module that new labels belong to, or leaves all modules if `None`.
+    fn set_module(&mut self, module: Option<String>);
+    fn open_module(&mut self, module: &str);
+    /// Returns the opened modules in the order they were opened.
+    fn opened_modules(&self) -> Vec<String>;
+    /// Returns the label that a new symbol is given, which is qualified by the current module unless the symbol is already qualified, is global or is a pattern variable. A global symbol is labelled without its leading separator.
+    fn qualify(&self, symbol: &str) -> String {
+        if let Some(label) = global(symbol) {
+            return label.to_string();
+        }
+        match self.get_module() {
+            Some(ref module) if !is_qualified(symbol) && !is_variable_symbol(symbol) => {
+                module.clone() + SEPARATOR + symbol
+            }
+            _ => symbol.to_string(),
+        }
+    }
+    /// Returns the labels that a symbol may refer to in order of precedence: a label in the current module, a label outside of any module and then a label in each opened module. A qualified symbol only refers to its own label and a global symbol only refers to its label outside of any module.
+    fn qualifications(&self, symbol: &str) -> Vec<String> {
+        if let Some(label) = global(symbol) {
+            return vec![label.to_string()];
+        }
+        if is_qualified(symbol) {
+            return vec![symbol.to_string()];
+        }
+        let mut qualifications = Vec::new();
+        if let Some(module) = self.get_module() {
+            qualifications.push(module + SEPARATOR + symbol);
+        }
+        qualifications.push(symbol.to_string());
+        for module in self.opened_modules() {
+            qualifications.push(module + SEPARATOR + symbol);
+        }
+        qualifications
+    }
+}
+
+/// Shortening labels for display.
+pub trait Unqualify {
+    /// Returns the label without its module if the unqualified symbol refers to the same concept.
+    fn unqualify(&self, label: String) -> String;
+}
+
+/// Whether the symbol names its module, e.g. `logic::not`.
+pub fn is_qualified(symbol: &str) -> bool {
+    match symbol.rfind(SEPARATOR) {
+        Some(position) => position > 0 && position + SEPARATOR.len() < symbol.len(),
+        None => false,
+    }
+}
+
+/// Returns the symbol without its leading separator if it refers to a label outside of any module, e.g. `::not`.
+pub fn global(symbol: &str) -> Option<&str> {
+    symbol
+        .strip_prefix(SEPARATOR)
+        .filter(|rest| !rest.is_empty())
+}
+
+/// Returns the symbol without its module.
+pub fn unqualified(symbol: &str) -> &str {
+    if is_qualified(symbol) {
+        symbol.rsplit(SEPARATOR).next().unwrap_or(symbol)
+    } else {
+        symbol
+    }
+}
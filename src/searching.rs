@@ -0,0 +1,264 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+use reading::{FindWhatReducesToIt, GetDefinition, GetReduction, IsConcrete, Label, MaybeString};
+use translating::StringConcepts;
+
+/// The kinds of concepts that a label search can be restricted to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConceptKind {
+    /// Concepts that can't be reduced or defined, such as built-in concepts and integers.
+    Concrete,
+    /// Concepts that can be reduced and defined.
+    Abstract,
+    /// Strings that don't label a concept, e.g. previous labels. They are only found when searching for this kind.
+    String,
+}
+
+/// How the pattern of a label search is written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PatternSyntax {
+    /// `*` matches any sequence of characters, `?` matches any character and the pattern must match the whole label.
+    Glob,
+    /// `.` matches any character, `*` matches any number of the preceding character, `^` and `$` anchor the pattern to the start and end of the label, and the pattern may match any part of the label.
+    Regex,
+}
+
+/// A search for labels matching a pattern, optionally restricted to the labels of certain concepts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LabelSearch {
+    pattern: String,
+    syntax: PatternSyntax,
+    kind: Option<ConceptKind>,
+    definition: Option<bool>,
+    reduction: Option<bool>,
+}
+
+impl LabelSearch {
+    /// A search for labels matching a glob, e.g. `a*`.
+    pub fn glob(pattern: &str) -> LabelSearch {
+        LabelSearch::new(pattern, PatternSyntax::Glob)
+    }
+    /// A search for labels matching a simple regular expression, e.g. `^a.*b$`.
+    pub fn regex(pattern: &str) -> LabelSearch {
+        LabelSearch::new(pattern, PatternSyntax::Regex)
+    }
+    fn new(pattern: &str, syntax: PatternSyntax) -> LabelSearch {
+        LabelSearch {
+            pattern: pattern.to_string(),
+            syntax,
+            kind: None,
+            definition: None,
+            reduction: None,
+        }
+    }
+    /// Only finds the labels of concepts of this kind.
+    pub fn kind(mut self, kind: ConceptKind) -> LabelSearch {
+        self.kind = Some(kind);
+        self
+    }
+    /// Only finds the labels of concepts that have, or don't have, a definition.
+    pub fn with_definition(mut self, has_definition: bool) -> LabelSearch {
+        self.definition = Some(has_definition);
+        self
+    }
+    /// Only finds the labels of concepts that have, or don't have, a reduction rule.
+    pub fn with_reduction(mut self, has_reduction: bool) -> LabelSearch {
+        self.reduction = Some(has_reduction);
+        self
+    }
+    /// Whether the label matches the pattern.
+    pub fn matches(&self, label: &str) -> bool {
+        let pattern: Vec<char> = self.pattern.chars().collect();
+        let label: Vec<char> = label.chars().collect();
+        match self.syntax {
+            PatternSyntax::Glob => glob_matches(&pattern, &label),
+            PatternSyntax::Regex => regex_matches(&pattern, &label),
+        }
+    }
+    /// Whether a concept of this kind, with or without a definition and reduction rule, is searched for.
+    fn accepts(&self, kind: ConceptKind, has_definition: bool, has_reduction: bool) -> bool {
+        let kind_accepted = match self.kind {
+            Some(k) => k == kind,
+            None => kind != ConceptKind::String,
+        };
+        kind_accepted
+            && self.definition.is_none_or(|d| d == has_definition)
+            && self.reduction.is_none_or(|r| r == has_reduction)
+    }
+}
+
+/// Searching the labels of the context.
+pub trait SearchLabels<T>
+where
+    T: GetDefinition + GetReduction + FindWhatReducesToIt + MaybeString + IsConcrete,
+    Self: StringConcepts + Label<T>,
+{
+    /// Returns the labels found by the search in alphabetical order.
+    fn search_labels(&self, search: &LabelSearch) -> Vec<String> {
+        let mut labels = Vec::new();
+        for (_, string_concept) in self.string_concepts() {
+            let label = match self.read_concept(string_concept).get_string() {
+                Some(l) => l,
+                None => continue,
+            };
+            if !search.matches(&label) {
+                continue;
+            }
+            let (concept, kind) = match self.get_labellee(string_concept) {
+                Some(c) if self.read_concept(c).is_concrete() => (c, ConceptKind::Concrete),
+                Some(c) => (c, ConceptKind::Abstract),
+                None => (string_concept, ConceptKind::String),
+            };
+            let read_concept = self.read_concept(concept);
+            if search.accepts(
+                kind,
+                read_concept.get_definition().is_some(),
+                read_concept.get_reduction().is_some(),
+            ) {
+                labels.push(label);
+            }
+        }
+        labels
+    }
+}
+
+impl<S, T> SearchLabels<T> for S
+where
+    T: GetDefinition + GetReduction + FindWhatReducesToIt + MaybeString + IsConcrete,
+    S: StringConcepts + Label<T>,
+{
+}
+
+/// Matches the text against the glob by going back to the last `*` after a mismatch, which takes at most quadratic time.
+fn glob_matches(pattern: &[char], text: &[char]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    let mut last_star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some(&'*') => {
+                last_star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match last_star {
+                Some((star, matched)) => {
+                    last_star = Some((star, matched + 1));
+                    p = star + 1;
+                    t = matched + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// A character of a regular expression, where `None` is `.`, and whether it is followed by `*`.
+type RegexToken = (Option<char>, bool);
+
+/// Matches the text against the regular expression by keeping track of every token that could be reached so far, which takes time proportional to the lengths of the text and the regular expression multiplied together.
+fn regex_matches(pattern: &[char], text: &[char]) -> bool {
+    let (anchored, pattern) = match pattern.split_first() {
+        Some((&'^', rest)) => (true, rest),
+        _ => (false, pattern),
+    };
+    let (tokens, at_end) = regex_tokens(pattern);
+    let mut states = vec![false; tokens.len() + 1];
+    add_regex_state(&tokens, &mut states, 0);
+    for c in text {
+        if states[tokens.len()] && !at_end {
+            return true;
+        }
+        let mut next = vec![false; tokens.len() + 1];
+        for (state, &(expected, repeated)) in tokens.iter().enumerate() {
+            if states[state] && expected.is_none_or(|e| e == *c) {
+                add_regex_state(&tokens, &mut next, if repeated { state } else { state + 1 });
+            }
+        }
+        if !anchored {
+            add_regex_state(&tokens, &mut next, 0);
+        }
+        states = next;
+    }
+    states[tokens.len()]
+}
+
+/// Splits the regular expression into tokens and whether it ends with `$`.
+fn regex_tokens(pattern: &[char]) -> (Vec<RegexToken>, bool) {
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < pattern.len() {
+        let expected = if pattern[i] == '.' {
+            None
+        } else {
+            Some(pattern[i])
+        };
+        if pattern.get(i + 1) == Some(&'*') {
+            tokens.push((expected, true));
+            i += 2;
+        } else if i + 1 == pattern.len() && pattern[i] == '$' {
+            return (tokens, true);
+        } else {
+            tokens.push((expected, false));
+            i += 1;
+        }
+    }
+    (tokens, false)
+}
+
+/// Marks the token as reached along with the tokens after it that can be reached by repeating the tokens followed by `*` no times.
+fn add_regex_state(tokens: &[RegexToken], states: &mut [bool], mut state: usize) {
+    loop {
+        states[state] = true;
+        match tokens.get(state) {
+            Some(&(_, true)) => state += 1,
+            _ => return,
+        }
+    }
+}
+
+#[cfg(test)]
+mod patterns {
+    use super::LabelSearch;
+    #[test]
+    fn glob() {
+        let search = LabelSearch::glob("a*b?");
+        assert!(search.matches("abc"));
+        assert!(search.matches("axxbé"));
+        assert!(!search.matches("ab"));
+        assert!(!search.matches("xabc"));
+    }
+    #[test]
+    fn regex() {
+        assert!(LabelSearch::regex("b.d").matches("abcde"));
+        assert!(LabelSearch::regex("^ab*c$").matches("abbbc"));
+        assert!(LabelSearch::regex("^ab*c$").matches("ac"));
+        assert!(!LabelSearch::regex("^ab*c$").matches("abcd"));
+        assert!(!LabelSearch::regex("^b").matches("abc"));
+    }
+    #[test]
+    fn many_stars() {
+        let text = "a".repeat(100);
+        assert!(!LabelSearch::glob(&("a*".repeat(30) + "c")).matches(&(text.clone() + "b")));
+        assert!(LabelSearch::glob(&("a*".repeat(30) + "b")).matches(&(text.clone() + "b")));
+        assert!(!LabelSearch::regex(&("^".to_string() + &"a*".repeat(30) + "b$")).matches(&text));
+        assert!(LabelSearch::regex(&("a*".repeat(30) + "$")).matches(&text));
+    }
+}
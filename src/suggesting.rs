@@ -0,0 +1,114 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+use reading::{FindWhatReducesToIt, GetDefinition, Label};
+use translating::StringConcepts;
+
+/// The most labels suggested for a symbol.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// The fewest characters of a new symbol that can be rejected for being similar to an existing label. Shorter symbols are similar to too many labels for this to suggest a typo.
+pub const MIN_SIMILAR_SYMBOL_LENGTH: usize = 4;
+
+/// Which commands are rejected if they contain symbols that aren't used.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Strictness {
+    /// Symbols that aren't used are accepted everywhere.
+    #[default]
+    Lenient,
+    /// Queries, i.e. commands that don't modify the context, are rejected if they contain a symbol that isn't used.
+    Queries,
+    /// Queries are rejected as above and other commands are also rejected if they contain a new symbol of at least `MIN_SIMILAR_SYMBOL_LENGTH` characters that is similar to an existing label, e.g. because of a typo.
+    Commands,
+}
+
+/// Getting which commands are rejected if they contain symbols that aren't used.
+pub trait GetStrictness {
+    fn get_strictness(&self) -> Strictness;
+}
+
+/// Setting which commands are rejected if they contain symbols that aren't used.
+pub trait SetStrictness {
+    fn set_strictness(&mut self, strictness: Strictness);
+}
+
+/// Finding the labels that are similar to a symbol.
+pub trait SuggestLabels<T>
+where
+    T: GetDefinition + FindWhatReducesToIt,
+    Self: StringConcepts + Label<T>,
+{
+    /// Returns up to three labels of concepts that are closest to the symbol by edit distance, closest first. Labels that differ from the symbol in more than about a third of its characters aren't suggested, so nothing is suggested for symbols of one character.
+    fn suggest_labels(&self, symbol: &str) -> Vec<String> {
+        let max_distance = (symbol.chars().count() + 1) / 3;
+        let mut suggestions: Vec<(usize, String)> = self
+            .string_concepts()
+            .into_iter()
+            .filter(|&(_, concept)| self.get_labellee(concept).is_some())
+            .map(|(label, _)| (edit_distance(symbol, &label), label))
+            .filter(|&(distance, _)| distance > 0 && distance <= max_distance)
+            .collect();
+        suggestions.sort();
+        suggestions
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, label)| label)
+            .collect()
+    }
+}
+
+impl<S, T> SuggestLabels<T> for S
+where
+    T: GetDefinition + FindWhatReducesToIt,
+    S: StringConcepts + Label<T>,
+{
+}
+
+/// The fewest insertions, deletions or substitutions of characters that turn one string into the other.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + if ca == *cb { 0 } else { 1 };
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod distances {
+    use super::edit_distance;
+    #[test]
+    fn same() {
+        assert_eq!(edit_distance("abc", "abc"), 0);
+    }
+    #[test]
+    fn insertion() {
+        assert_eq!(edit_distance("b", "bb"), 1);
+    }
+    #[test]
+    fn substitution_and_deletion() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+    #[test]
+    fn unicode() {
+        assert_eq!(edit_distance("préférer", "preferer"), 2);
+    }
+}
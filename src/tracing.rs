@@ -0,0 +1,45 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+use std::fmt;
+
+/// A rewrite of a sub-expression made by a reduction rule.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReductionStep {
+    /// The sub-expression before it was rewritten.
+    pub before: String,
+    /// The concept whose reduction rule was used.
+    pub rule: String,
+    /// The sub-expression after it was rewritten.
+    pub after: String,
+}
+
+impl fmt::Display for ReductionStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} -> {} ({})", self.before, self.after, self.rule)
+    }
+}
+
+/// Recording the reduction steps made while tracing.
+pub trait Trace {
+    /// Starts recording reduction steps, discarding any steps that were previously recorded.
+    fn start_tracing(&self);
+    fn is_tracing(&self) -> bool;
+    /// Records a reduction step if tracing.
+    fn record_step(&self, step: ReductionStep);
+    /// Stops recording reduction steps and returns the steps that were recorded.
+    fn stop_tracing(&self) -> Vec<ReductionStep>;
+}
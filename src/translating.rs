@@ -15,11 +15,16 @@
     along with this program. If not, see <http://www.gnu.org/licenses/>.
 */
 
+use aliasing::Aliases;
+use ast::Expr;
 use errors::{ZiaError, ZiaResult};
+use localising::Translations;
 use reading::{
     Combine, DisplayJoint, FindWhatReducesToIt, GetDefinition, GetDefinitionOf, Label,
     MaybeConcept, Pair,
 };
+use renaming::LabelHistory;
+use scoping::Modules;
 use std::rc::Rc;
 
 pub trait SyntaxConverter<T>
@@ -60,6 +65,26 @@ where
             Ok(Rc::new(self.ast_from_symbol::<U>(t)))
         }
     }
+    /// Links the symbols of a programmatically built expression to concepts. Symbols that could not be written in a command are rejected.
+    fn ast_from_expr<U: From<(String, Option<usize>)> + MaybeConcept + DisplayJoint + Pair<U>>(
+        &self,
+        expr: &Expr,
+    ) -> ZiaResult<Rc<U>> {
+        match *expr {
+            Expr::Symbol(ref s) => {
+                if is_valid_symbol(s) {
+                    Ok(Rc::new(self.ast_from_symbol::<U>(s)))
+                } else {
+                    Err(ZiaError::InvalidSymbol)
+                }
+            }
+            Expr::Pair(ref left, ref right) => {
+                let lefthand = try!(self.ast_from_expr(left));
+                let righthand = try!(self.ast_from_expr(right));
+                Ok(self.combine(&lefthand, &righthand))
+            }
+        }
+    }
 }
 
 impl<S, T> SyntaxConverter<T> for S
@@ -88,6 +113,21 @@ mod tokens {
             ["(lambda x_)(_f _x)", "_y", "->"].to_vec()
         );
     }
+    #[test]
+    fn unicode_whitespace() {
+        assert_eq!(
+            parse_line("let\t(a\u{3000}(->\nb))"),
+            ["let", "a (-> b)"].to_vec()
+        );
+    }
+}
+
+/// Whether a symbol could be written in a command, i.e. it is not empty and contains no whitespace or parentheses.
+pub fn is_valid_symbol(symbol: &str) -> bool {
+    !symbol.is_empty()
+        && !symbol
+            .chars()
+            .any(|c| c.is_whitespace() || c == '(' || c == ')')
 }
 
 pub fn parse_line(buffer: &str) -> Vec<String> {
@@ -118,8 +158,7 @@ fn parse_letter(
             *parenthesis_level -= 1;
             push_token(letter, *parenthesis_level, token, tokens);
         }
-        ' ' => push_token(letter, *parenthesis_level, token, tokens),
-        '\n' | '\r' => (),
+        _ if letter.is_whitespace() => push_token(' ', *parenthesis_level, token, tokens),
         _ => token.push(letter),
     };
 }
@@ -136,24 +175,47 @@ fn push_token(letter: char, parenthesis_level: i8, token: &mut String, tokens: &
 
 pub trait SyntaxFinder<T>
 where
-    Self: StringConcept + Label<T>,
+    Self: StringConcept + Label<T> + Aliases + Modules + Translations + LabelHistory,
     T: FindWhatReducesToIt + GetDefinition,
 {
+    /// Returns the concept that the symbol refers to in the current scope.
     fn concept_from_label(&self, s: &str) -> Option<usize> {
-        match self.get_string_concept(s) {
-            None => None,
-            Some(c) => self.get_labellee(c),
+        self.qualifications(s)
+            .iter()
+            .filter_map(|label| self.concept_from_exact_label(label))
+            .next()
+    }
+    /// Returns the concept labelled by the symbol in the active or a fallback language or, failing that, the concept labelled by the symbol or the concept it is an alias of.
+    fn concept_from_exact_label(&self, s: &str) -> Option<usize> {
+        self.concept_from_translation(s)
+            .or_else(|| {
+                self.get_string_concept(s)
+                    .and_then(|c| self.get_labellee(c))
+            })
+            .or_else(|| self.get_aliased(s))
+    }
+    /// If previous labels are read, returns the concept that the symbol labelled before the concept was relabelled and records this use of the previous label.
+    fn concept_from_previous_label(&self, s: &str) -> Option<usize> {
+        if !self.resolves_previous_labels() {
+            return None;
         }
+        let concept = self.get_renamed(s);
+        if let Some(c) = concept {
+            self.record_renamed_use(s, c);
+        }
+        concept
     }
     fn ast_from_symbol<U: From<(String, Option<usize>)>>(&self, s: &str) -> U {
-        let concept_if_exists = self.concept_from_label(s);
+        let concept_if_exists = self
+            .concept_from_label(s)
+            .or_else(|| self.concept_from_previous_label(s));
         U::from((s.to_string(), concept_if_exists))
     }
 }
 
 impl<S, T> SyntaxFinder<T> for S
 where
-    S: StringConcept + Label<T>,
+    S: StringConcept + Label<T> + Aliases + Modules + Translations + LabelHistory,
     T: FindWhatReducesToIt + GetDefinition,
 {
 }
@@ -161,3 +223,11 @@ where
 pub trait StringConcept {
     fn get_string_concept(&self, &str) -> Option<usize>;
 }
+
+/// Listing every string concept.
+pub trait StringConcepts {
+    /// Returns the string and index of each string concept in order of string.
+    fn string_concepts(&self) -> Vec<(String, usize)>;
+    /// Returns the string and index of each string concept whose normalised string starts with `prefix`, in order of string.
+    fn string_concepts_starting_with(&self, prefix: &str) -> Vec<(String, usize)>;
+}
@@ -15,12 +15,13 @@
     along with this program. If not, see <http://www.gnu.org/licenses/>.
 */
 
+use constants::LABEL;
 pub use errors::{ZiaError, ZiaResult};
 pub use reading::{
     ConceptReader, FindDefinition, GetDefinition, GetDefinitionOf, GetNormalForm, GetReduction,
     MaybeConcept,
 };
-use reading::{Container, GetConceptOfLabel};
+use reading::{Container, Display, GetConceptOfLabel, MaybeString, PatternRules, Patterns};
 pub trait Unlabeller<T>
 where
     T: GetReduction + RemoveReduction + NoLongerReducesFrom + GetDefinition + GetDefinitionOf,
@@ -44,7 +45,7 @@ where
 pub trait DeleteReduction<T>
 where
     T: GetReduction + RemoveReduction + NoLongerReducesFrom,
-    Self: ConceptWriter<T> + ConceptReader<T>,
+    Self: ConceptWriter<T> + ConceptReader<T> + PatternRules,
 {
     fn try_removing_reduction<U: MaybeConcept>(&mut self, syntax: &U) -> ZiaResult<()> {
         if let Some(c) = syntax.get_concept() {
@@ -59,6 +60,7 @@ where
             Some(n) => {
                 self.write_concept(n).no_longer_reduces_from(concept);
                 self.write_concept(concept).make_reduce_to_none();
+                self.remove_pattern_rule(concept);
                 Ok(())
             }
         }
@@ -67,7 +69,7 @@ where
 
 impl<S, T> DeleteReduction<T> for S
 where
-    S: ConceptWriter<T> + ConceptReader<T>,
+    S: ConceptWriter<T> + ConceptReader<T> + PatternRules,
     T: GetReduction + RemoveReduction + NoLongerReducesFrom,
 {
 }
@@ -93,13 +95,18 @@ where
 
 pub trait UpdateReduction<T>
 where
-    T: SetReduction + MakeReduceFrom + GetReduction + GetDefinition + GetDefinitionOf,
-    Self: ConceptWriter<T> + GetNormalForm<T> + FindDefinition<T>,
+    T: SetReduction + MakeReduceFrom + GetReduction + GetDefinition + GetDefinitionOf + MaybeString,
+    Self: ConceptWriter<T> + GetNormalForm<T> + FindDefinition<T> + Display<T> + Patterns<T>,
 {
+    /// Makes the concept reduce to `reduction` unless that would make a chain of reduction rules loop, already reduces the concept or conflicts with the reduction rules of its components. The label of a concept, i.e. `label_of` composed with the concept, never conflicts with the reduction rules of the concept or its components. A concept containing pattern variables becomes the last pattern rule to be tried, unless it already is a pattern rule.
     fn update_reduction(&mut self, concept: usize, reduction: usize) -> ZiaResult<()> {
         if let Some(n) = self.get_normal_form(reduction) {
             if concept == n {
-                return Err(ZiaError::CyclicReduction);
+                let mut chain = vec![self.display(concept)];
+                for c in self.get_reduction_chain(reduction) {
+                    chain.push(self.display(c));
+                }
+                return Err(ZiaError::CyclicReduction { chain });
             }
         }
         if let Some(r) = self.read_concept(concept).get_reduction() {
@@ -107,49 +114,92 @@ where
                 return Err(ZiaError::RedundantReduction);
             }
         }
-        let r = try!(self.get_reduction_of_composition(concept));
-        if r == reduction {
-            return Err(ZiaError::RedundantReduction);
-        } else if r != concept {
-            return Err(ZiaError::MultipleReductionPaths);
-        }
+        match self.get_reduction_of_composition(concept) {
+            _ if self.is_label_of_concept(concept) => (),
+            Some(r) => {
+                if r == reduction {
+                    return Err(ZiaError::RedundantReduction);
+                } else if r != concept {
+                    return Err(self.multiple_reduction_paths(concept));
+                }
+            }
+            None => return Err(self.multiple_reduction_paths(concept)),
+        };
         try!(self.write_concept(concept).make_reduce_to(reduction));
         self.write_concept(reduction).make_reduce_from(concept);
+        if self.contains_variable(concept) {
+            self.add_pattern_rule(concept);
+        }
         Ok(())
     }
-    fn get_reduction_of_composition(&self, concept: usize) -> ZiaResult<usize> {
+    /// Returns the concept composed of the reductions of the components of `concept` if such a concept exists.
+    fn get_reduction_of_composition(&self, concept: usize) -> Option<usize> {
         if let Some((left, right)) = self.read_concept(concept).get_definition() {
-            let lc = if let Some(l) = self.read_concept(left).get_reduction() {
-                l
-            } else {
-                try!(self.get_reduction_of_composition(left))
-            };
-            let rc = if let Some(r) = self.read_concept(left).get_reduction() {
-                r
-            } else {
-                try!(self.get_reduction_of_composition(right))
-            };
-            match self.find_definition(lc, rc) {
-                Some(dc) => Ok(dc),
-                None => Err(ZiaError::MultipleReductionPaths),
+            let lc = self
+                .read_concept(left)
+                .get_reduction()
+                .or_else(|| self.get_reduction_of_composition(left));
+            let rc = self
+                .read_concept(right)
+                .get_reduction()
+                .or_else(|| self.get_reduction_of_composition(right));
+            match (lc, rc) {
+                (Some(l), Some(r)) => self.find_definition(l, r),
+                _ => None,
             }
         } else {
-            Ok(concept)
+            Some(concept)
+        }
+    }
+    /// Returns the components of `concept` that have their own reduction rules.
+    fn find_reducing_components(&self, concept: usize) -> Vec<usize> {
+        let mut components = Vec::new();
+        if let Some((left, right)) = self.read_concept(concept).get_definition() {
+            for component in &[left, right] {
+                if self.read_concept(*component).get_reduction().is_some() {
+                    components.push(*component);
+                } else {
+                    components.extend(self.find_reducing_components(*component));
+                }
+            }
+        }
+        components
+    }
+    fn is_label_of_concept(&self, concept: usize) -> bool {
+        match self.read_concept(concept).get_definition() {
+            Some((left, _)) => left == LABEL,
+            None => false,
+        }
+    }
+    fn multiple_reduction_paths(&self, concept: usize) -> ZiaError {
+        ZiaError::MultipleReductionPaths {
+            concept: self.display(concept),
+            components: self
+                .find_reducing_components(concept)
+                .iter()
+                .map(|c| self.display(*c))
+                .collect(),
         }
     }
 }
 
 impl<S, T> UpdateReduction<T> for S
 where
-    T: SetReduction + MakeReduceFrom + GetReduction + GetDefinition + GetDefinitionOf,
-    S: ConceptWriter<T> + GetNormalForm<T> + FindDefinition<T>,
+    T: SetReduction + MakeReduceFrom + GetReduction + GetDefinition + GetDefinitionOf + MaybeString,
+    S: ConceptWriter<T> + GetNormalForm<T> + FindDefinition<T> + Display<T> + Patterns<T>,
 {
 }
 
 pub trait InsertDefinition<T>
 where
-    T: SetDefinition + SetAsDefinitionOf + Sized + GetDefinition + GetReduction,
-    Self: ConceptWriter<T> + Container<T>,
+    T: SetDefinition
+        + SetAsDefinitionOf
+        + Sized
+        + GetDefinition
+        + GetDefinitionOf
+        + GetReduction
+        + MaybeString,
+    Self: ConceptWriter<T> + Container<T> + Display<T>,
 {
     fn insert_definition(
         &mut self,
@@ -174,7 +224,10 @@ where
     fn check_reductions(&self, outer_concept: usize, inner_concept: usize) -> ZiaResult<()> {
         if let Some(r) = self.read_concept(inner_concept).get_reduction() {
             if r == outer_concept || self.contains(r, outer_concept) {
-                Err(ZiaError::ExpandingReduction)
+                Err(ZiaError::ExpandingReduction {
+                    concept: self.display(inner_concept),
+                    reduction: self.display(r),
+                })
             } else {
                 self.check_reductions(outer_concept, r)
             }
@@ -186,8 +239,14 @@ where
 
 impl<S, T> InsertDefinition<T> for S
 where
-    T: SetDefinition + SetAsDefinitionOf + Sized + GetDefinition + GetReduction,
-    S: ConceptWriter<T> + Container<T>,
+    T: SetDefinition
+        + SetAsDefinitionOf
+        + Sized
+        + GetDefinition
+        + GetDefinitionOf
+        + GetReduction
+        + MaybeString,
+    S: ConceptWriter<T> + Container<T> + Display<T>,
 {
 }
 
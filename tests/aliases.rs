@@ -0,0 +1,124 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate zia;
+
+mod common;
+
+use zia::{ContextBuilder, Execute, Feature, ZiaError};
+
+#[test]
+fn alias_refers_to_concept() {
+    let mut cont = common::context_with(Feature::Aliases);
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("let (c (alias_of a))"), "");
+    assert_eq!(cont.execute("c ->"), "b");
+    assert_eq!(cont.execute("let (d (-> c))"), "");
+    assert_eq!(cont.execute("d ->"), "a");
+}
+#[test]
+fn alias_of_new_concept() {
+    let mut cont = common::context_with(Feature::Aliases);
+    assert_eq!(cont.execute("let (c (alias_of a))"), "");
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("c ->"), "b");
+}
+#[test]
+fn several_aliases() {
+    let mut cont = common::context_with(Feature::Aliases);
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("let (c (alias_of a))"), "");
+    assert_eq!(cont.execute("let (d (alias_of c))"), "");
+    assert_eq!(cont.execute("d ->"), "b");
+}
+#[test]
+fn redundant_alias() {
+    let mut cont = common::context_with(Feature::Aliases);
+    assert_eq!(cont.execute("let (c (alias_of a))"), "");
+    assert_eq!(
+        cont.execute_structured("let (c (alias_of a))"),
+        Err(ZiaError::RedundantAlias)
+    );
+    assert_eq!(
+        cont.execute_structured("let (a (alias_of a))"),
+        Err(ZiaError::RedundantAlias)
+    );
+}
+#[test]
+fn used_symbol_as_alias() {
+    let mut cont = common::context_with(Feature::Aliases);
+    assert_eq!(cont.execute("let (b (-> d))"), "");
+    assert_eq!(
+        cont.execute_structured("let (b (alias_of a))"),
+        Err(ZiaError::DefinitionCollision {
+            symbol: "b".to_string(),
+            expression: "a".to_string(),
+        })
+    );
+}
+#[test]
+fn expression_as_alias() {
+    let mut cont = common::context_with(Feature::Aliases);
+    assert_eq!(
+        cont.execute_structured("let ((c d) (alias_of a))"),
+        Err(ZiaError::BadDefinition)
+    );
+}
+#[test]
+fn remove_alias() {
+    let mut cont = common::context_with(Feature::Aliases);
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("let (c (alias_of a))"), "");
+    assert_eq!(cont.execute("unalias c"), "");
+    assert_eq!(cont.execute("c ->"), "c");
+    assert_eq!(cont.execute("a ->"), "b");
+    assert_eq!(
+        cont.execute_structured("unalias c"),
+        Err(ZiaError::UnknownAlias {
+            symbol: "c".to_string()
+        })
+    );
+    assert_eq!(
+        cont.execute_structured("unalias a"),
+        Err(ZiaError::UnknownAlias {
+            symbol: "a".to_string()
+        })
+    );
+}
+#[test]
+fn prefer_alias() {
+    let mut cont = common::context_with(Feature::Aliases);
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("let (c (alias_of b))"), "");
+    assert_eq!(cont.execute("prefer c"), "");
+    assert_eq!(cont.execute("a ->"), "c");
+    assert_eq!(cont.execute("let (d (-> b))"), "");
+    assert_eq!(cont.execute("d ->"), "c");
+    assert_eq!(cont.execute("unalias b"), "");
+    assert_eq!(cont.execute("a ->"), "c");
+}
+#[test]
+fn aliases_of_deleted_concept() {
+    let mut cont = ContextBuilder::new()
+        .feature(Feature::Aliases)
+        .feature(Feature::Deletion)
+        .build()
+        .unwrap();
+    assert_eq!(cont.execute("let (c (alias_of a))"), "");
+    assert_eq!(cont.execute("delete c"), "");
+    assert_eq!(cont.execute("c ->"), "c");
+    assert_eq!(cont.execute("a ->"), "a");
+}
@@ -0,0 +1,82 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate zia;
+
+mod common;
+
+use zia::{Context, ContextMaker, Execute, Feature, ZiaError};
+
+#[test]
+fn addition() {
+    let mut cont = common::context_with(Feature::Arithmetic);
+    assert_eq!(cont.execute("(label_of ((2 (+ 3)) ->)) ->"), "5");
+    assert_eq!(cont.execute("(2 (+ 3)) ->"), "5");
+}
+#[test]
+fn subtraction_and_multiplication() {
+    let mut cont = common::context_with(Feature::Arithmetic);
+    assert_eq!(cont.execute("(2 (- 3)) ->"), "-1");
+    assert_eq!(cont.execute("(-4 (* 3)) ->"), "-12");
+}
+#[test]
+fn nested_operations() {
+    let mut cont = common::context_with(Feature::Arithmetic);
+    assert_eq!(cont.execute("((2 (+ 3)) (* 4)) ->"), "5 (* 4)");
+    assert_eq!(cont.execute("((2 (+ 3)) (* 4)) ->*"), "20");
+    assert_eq!(cont.execute("(2 (+ (3 (* 4)))) ->*"), "14");
+}
+#[test]
+fn comparison() {
+    let mut cont = common::context_with(Feature::Arithmetic);
+    assert_eq!(cont.execute("(2 (< 3)) ->"), "true");
+    assert_eq!(cont.execute("(2 (> 3)) ->"), "false");
+    assert_eq!(cont.execute("(3 (> 3)) ->"), "false");
+}
+#[test]
+fn rules_with_integers() {
+    let mut cont = common::context_with(Feature::Arithmetic);
+    assert_eq!(cont.execute("let (ten (-> 10))"), "");
+    assert_eq!(cont.execute("(ten (+ 1)) ->*"), "11");
+    assert_eq!(cont.execute("let ((double _x) (-> (_x (* 2))))"), "");
+    assert_eq!(cont.execute("(double 21) ->*"), "42");
+}
+#[test]
+fn integers_are_concrete() {
+    let mut cont = common::context_with(Feature::Arithmetic);
+    assert_eq!(
+        cont.execute("let (2 (-> 3))"),
+        ZiaError::ConcreteReduction.to_string()
+    );
+    assert_eq!(
+        cont.execute("let (2 (:= (a b)))"),
+        ZiaError::SettingDefinitionOfConcrete.to_string()
+    );
+}
+#[test]
+fn overflow_does_not_reduce() {
+    let mut cont = common::context_with(Feature::Arithmetic);
+    assert_eq!(
+        cont.execute("(9223372036854775807 (+ 1)) ->"),
+        "9223372036854775807 (+ 1)"
+    );
+}
+#[test]
+fn numbers_are_labels_without_feature() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (2 (-> 3))"), "");
+    assert_eq!(cont.execute("(label_of (2 ->)) ->"), "3");
+}
@@ -0,0 +1,57 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate zia;
+
+mod common;
+
+use zia::{ContextBuilder, Execute, Feature};
+
+#[test]
+fn same_concept() {
+    let mut cont = common::context_with(Feature::Booleans);
+    assert_eq!(cont.execute("(a (= a)) ->"), "true");
+    assert_eq!(cont.execute("(a (= b)) ->"), "false");
+    assert_eq!(cont.execute("((a b) (= (a b))) ->"), "true");
+}
+#[test]
+fn same_normal_form() {
+    let mut cont = common::context_with(Feature::Booleans);
+    assert_eq!(cont.execute("let (a (-> c))"), "");
+    assert_eq!(cont.execute("let (b (-> c))"), "");
+    assert_eq!(cont.execute("(a (= b)) ->"), "true");
+    assert_eq!(cont.execute("((a d) (= (b d))) ->"), "true");
+    assert_eq!(cont.execute("((a d) (= (d b))) ->"), "false");
+}
+#[test]
+fn conditional_rules() {
+    let mut cont = common::context_with(Feature::Booleans);
+    assert_eq!(cont.execute("let ((true (and _x)) (-> _x))"), "");
+    assert_eq!(cont.execute("let ((false (and _x)) (-> false))"), "");
+    assert_eq!(cont.execute("((a (= a)) (and (b (= b)))) ->*"), "true");
+    assert_eq!(cont.execute("((a (= a)) (and (b (= c)))) ->*"), "false");
+    assert_eq!(cont.execute("((a (= c)) (and (b (= b)))) ->*"), "false");
+}
+#[test]
+fn with_arithmetic() {
+    let mut cont = ContextBuilder::new()
+        .feature(Feature::Arithmetic)
+        .feature(Feature::Booleans)
+        .build()
+        .unwrap();
+    assert_eq!(cont.execute("((2 (+ 2)) (= 4)) ->"), "true");
+    assert_eq!(cont.execute("((2 (< 3)) (= true)) ->"), "true");
+}
@@ -0,0 +1,143 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate zia;
+
+use zia::{
+    Builtin, Context, ContextBuilder, ContextMaker, Execute, Feature, Locale, Normalisation,
+    Output, ZiaError,
+};
+
+#[test]
+fn default_labels() {
+    let mut cont = ContextBuilder::new().build().unwrap();
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("(label_of (a ->)) ->"), "b");
+}
+#[test]
+fn chosen_labels() {
+    let mut cont = ContextBuilder::new()
+        .builtin_label(Builtin::Let, "set")
+        .builtin_label(Builtin::Reduction, "=>")
+        .build()
+        .unwrap();
+    assert_eq!(cont.execute("set (a (=> b))"), "");
+    assert_eq!(cont.execute("(label_of (a =>)) =>"), "b");
+    assert_eq!(
+        cont.execute("let (c (=> d))"),
+        ZiaError::NotAProgram.to_string()
+    );
+}
+#[test]
+fn chosen_labels_override_locale() {
+    let mut cont = ContextBuilder::new()
+        .locale(Locale::French)
+        .builtin_label(Builtin::Label, "label_of")
+        .build()
+        .unwrap();
+    assert_eq!(cont.execute("soit (a (-> b))"), "");
+    assert_eq!(cont.execute("(label_of (a ->)) ->"), "b");
+}
+#[test]
+fn additional_concrete_concepts() {
+    let builder = ContextBuilder::new().concrete("now").concrete("today");
+    assert_eq!(builder.reserved_id("now"), Some(64));
+    assert_eq!(builder.reserved_id("today"), Some(65));
+    assert_eq!(builder.reserved_id("tomorrow"), None);
+    let mut cont = builder.build().unwrap();
+    assert_eq!(cont.display_concept(64), Some("now".to_string()));
+    assert_eq!(cont.display_concept(63), None);
+    assert_eq!(
+        cont.execute("let (now (-> a))"),
+        ZiaError::ConcreteReduction.to_string()
+    );
+    assert_eq!(cont.execute("let (a (-> today))"), "");
+    assert_eq!(cont.execute("(label_of (a ->)) ->"), "today");
+}
+#[test]
+fn colliding_labels() {
+    match ContextBuilder::new().concrete("let").build() {
+        Err(ZiaError::DefinitionCollision { .. }) => (),
+        _ => panic!("Expected a definition collision"),
+    };
+    match ContextBuilder::new()
+        .concrete("caf\u{e9}")
+        .concrete("cafe\u{301}")
+        .build()
+    {
+        Err(ZiaError::DefinitionCollision { .. }) => (),
+        _ => panic!("Expected a definition collision"),
+    };
+    assert!(ContextBuilder::new()
+        .normalisation(Normalisation::None)
+        .concrete("caf\u{e9}")
+        .concrete("cafe\u{301}")
+        .build()
+        .is_ok());
+}
+#[test]
+fn invalid_labels() {
+    match ContextBuilder::new()
+        .builtin_label(Builtin::Let, "let it")
+        .build()
+    {
+        Err(ZiaError::InvalidSymbol) => (),
+        _ => panic!("Expected an invalid symbol"),
+    };
+}
+#[test]
+fn reserved_indices_have_no_concepts() {
+    let mut cont = Context::new();
+    for concept in 28..64 {
+        assert_eq!(cont.display_concept(concept), None);
+    }
+    match cont.execute_structured("let (a (-> b))") {
+        Ok(Output::Mutation(changes)) => {
+            assert!(changes.created.iter().all(|c| !(28..64).contains(c)))
+        }
+        _ => panic!("Expected a mutation"),
+    }
+}
+#[test]
+fn features_label_their_concepts() {
+    let features: &[(Feature, &[&str])] = &[
+        (
+            Feature::Introspection,
+            &["trace", "<-", "<-*", "used_in", "used_in*", "search"],
+        ),
+        (
+            Feature::Arithmetic,
+            &["+", "-", "*", "<", ">", "true", "false"],
+        ),
+        (Feature::Booleans, &["true", "false", "="]),
+        (Feature::Modules, &["module", "open"]),
+        (Feature::LabelHistory, &["previous_labels", "forget_labels"]),
+        (Feature::Deletion, &["delete", "delete*"]),
+        (Feature::Aliases, &["alias_of", "unalias", "prefer"]),
+    ];
+    for &(feature, symbols) in features {
+        let mut with_feature = ContextBuilder::new().feature(feature).build().unwrap();
+        let mut without_feature = Context::new();
+        for symbol in symbols {
+            let command = format!("let ({} (-> x))", symbol);
+            assert_eq!(
+                with_feature.execute(&command),
+                ZiaError::ConcreteReduction.to_string()
+            );
+            assert_eq!(without_feature.execute(&command), "");
+        }
+    }
+}
@@ -0,0 +1,102 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate zia;
+
+mod common;
+
+use zia::{
+    Complete, Completion, Context, ContextBuilder, ContextMaker, Execute, Feature, Locale,
+    Multilingual,
+};
+
+#[test]
+fn labels_with_prefix() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (apple (-> fruit))"), "");
+    assert_eq!(cont.execute("let (apply (-> fruit))"), "");
+    assert_eq!(cont.complete("app"), ["apple", "apply"]);
+    assert_eq!(cont.complete("apple"), ["apple"]);
+    assert_eq!(cont.complete("fr"), ["fruit"]);
+    assert!(cont.complete("b").is_empty());
+}
+#[test]
+fn builtin_labels() {
+    let cont = Context::new();
+    assert_eq!(cont.complete("->"), ["->", "->*"]);
+    assert_eq!(cont.complete("l"), ["label_of", "let"]);
+}
+#[test]
+fn only_current_labels() {
+    let mut cont = common::context_with(Feature::Deletion);
+    assert_eq!(cont.execute("let (apple (-> fruit))"), "");
+    assert_eq!(cont.execute("let (pear (:= apple))"), "");
+    assert!(cont.complete("app").is_empty());
+    assert_eq!(cont.complete("pe"), ["pear"]);
+    assert_eq!(cont.execute("let (c (-> d))"), "");
+    assert_eq!(cont.execute("delete c"), "");
+    assert!(cont.complete("c").is_empty());
+}
+#[test]
+fn completion_at_cursor() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (apple (-> fruit))"), "");
+    assert_eq!(
+        cont.complete_at("let (a (-> ap", 13),
+        Completion {
+            start: 11,
+            labels: vec!["apple".to_string()]
+        }
+    );
+    assert_eq!(
+        cont.complete_at("(ap fruit) ->", 3),
+        Completion {
+            start: 1,
+            labels: vec!["apple".to_string()]
+        }
+    );
+    assert_eq!(
+        cont.complete_at("apple (", 7),
+        Completion {
+            start: 7,
+            labels: cont.complete("")
+        }
+    );
+}
+#[test]
+fn labels_in_scope() {
+    let mut cont = ContextBuilder::new()
+        .feature(Feature::Modules)
+        .build()
+        .unwrap();
+    assert_eq!(cont.execute("module logic"), "");
+    assert_eq!(cont.execute("let (not (-> a))"), "");
+    assert_eq!(cont.execute("module ::"), "");
+    assert!(cont.complete("no").is_empty());
+    assert_eq!(cont.complete("logic::n"), ["logic::not"]);
+    assert_eq!(cont.execute("open logic"), "");
+    assert_eq!(cont.complete("no"), ["not"]);
+}
+#[test]
+fn aliases_and_translations() {
+    let mut cont = common::context_with(Feature::Aliases);
+    assert_eq!(cont.execute("let (not (-> a))"), "");
+    assert_eq!(cont.execute("let (negation (alias_of not))"), "");
+    assert_eq!(cont.label_in(Locale::French, "not", "non"), Ok(()));
+    assert_eq!(cont.complete("n"), ["negation", "not"]);
+    cont.set_language(Locale::French);
+    assert_eq!(cont.complete("n"), ["negation", "non", "not"]);
+}
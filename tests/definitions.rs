@@ -85,7 +85,11 @@ fn bad_refactor() {
     assert_eq!(cont.execute("let (a (:= (b c)))"), "");
     assert_eq!(
         cont.execute("let (b (:= a))"),
-        ZiaError::DefinitionCollision.to_string()
+        ZiaError::DefinitionCollision {
+            symbol: "b".to_string(),
+            expression: "a".to_string(),
+        }
+        .to_string()
     );
 }
 #[test]
@@ -95,7 +99,11 @@ fn defining_used_symbol_as_used_pair() {
     assert_eq!(cont.execute("let (f (:= (d e)))"), "");
     assert_eq!(
         cont.execute("let (d (:= (b c)))"),
-        ZiaError::DefinitionCollision.to_string()
+        ZiaError::DefinitionCollision {
+            symbol: "d".to_string(),
+            expression: "b c".to_string(),
+        }
+        .to_string()
     );
 }
 #[test]
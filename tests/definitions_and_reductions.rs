@@ -29,7 +29,11 @@ fn indirect_reduction() {
     assert_eq!(cont.execute("(label_of (a ->)) ->"), "f");
     assert_eq!(
         cont.execute("let (a (-> g))"),
-        ZiaError::MultipleReductionPaths.to_string()
+        ZiaError::MultipleReductionPaths {
+            concept: "a".to_string(),
+            components: vec!["b".to_string(), "c".to_string()],
+        }
+        .to_string()
     );
 }
 #[test]
@@ -38,7 +42,11 @@ fn sneeky_infinite_reduction_chain() {
     assert_eq!(cont.execute("let (c (-> a))"), "");
     assert_eq!(
         cont.execute("let (a (:= (c b)))"),
-        ZiaError::ExpandingReduction.to_string()
+        ZiaError::ExpandingReduction {
+            concept: "c".to_string(),
+            reduction: "a".to_string(),
+        }
+        .to_string()
     );
 }
 #[test]
@@ -48,6 +56,28 @@ fn reducing_nested_definition() {
     assert_eq!(cont.execute("let (c (-> e))"), "");
     assert_eq!(
         cont.execute("let (a (-> f))"),
-        ZiaError::MultipleReductionPaths.to_string()
+        ZiaError::MultipleReductionPaths {
+            concept: "a".to_string(),
+            components: vec!["c".to_string()],
+        }
+        .to_string()
     );
 }
+#[test]
+fn reduction_of_composition_with_irreducible_righthand() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("let (d (:= (b c)))"), "");
+    assert_eq!(
+        cont.execute("let ((a c) (-> d))"),
+        ZiaError::RedundantReduction.to_string()
+    );
+}
+#[test]
+fn displaying_nested_compositions() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (a (-> ((b c) d)))"), "");
+    assert_eq!(cont.execute("(label_of (a ->)) ->"), "(b c) d");
+    assert_eq!(cont.execute("let (e (-> (b (c d))))"), "");
+    assert_eq!(cont.execute("(label_of (e ->)) ->"), "b (c d)");
+}
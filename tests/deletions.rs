@@ -0,0 +1,107 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate zia;
+
+mod common;
+
+use zia::{ContextBuilder, Execute, Feature, Output, ZiaError};
+
+#[test]
+fn delete_unused_concept() {
+    let mut cont = common::context_with(Feature::Deletion);
+    let created = match cont.execute_structured("let (a (-> (b c)))") {
+        Ok(Output::Mutation(changes)) => changes.created,
+        _ => panic!("Expected a mutation"),
+    };
+    match cont.execute_structured("delete a") {
+        Ok(Output::Mutation(changes)) => assert_eq!(changes.removed, created),
+        _ => panic!("Expected a mutation"),
+    };
+    assert_eq!(cont.execute("let (a (-> (b c)))"), "");
+}
+#[test]
+fn reduce_delete() {
+    let mut cont = common::context_with(Feature::Deletion);
+    assert_eq!(cont.execute("delete ->"), "delete");
+    assert_eq!(cont.execute("delete* ->*"), "delete*");
+    assert_eq!(
+        cont.execute("let (delete (-> a))"),
+        ZiaError::ConcreteReduction.to_string()
+    );
+}
+#[test]
+fn delete_concept_with_dependents() {
+    let mut cont = common::context_with(Feature::Deletion);
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("let (c (:= (b d)))"), "");
+    assert_eq!(
+        cont.execute_structured("delete b"),
+        Err(ZiaError::DependentConcepts {
+            concept: "b".to_string(),
+            dependents: vec!["a".to_string(), "c".to_string()],
+        })
+    );
+    assert_eq!(cont.execute("a ->"), "b");
+    assert_eq!(
+        cont.execute("delete b"),
+        "Cannot delete `b` while other concepts depend on it: `a`, `c`."
+    );
+}
+#[test]
+fn cascading_delete() {
+    let mut cont = ContextBuilder::new()
+        .feature(Feature::Deletion)
+        .feature(Feature::Introspection)
+        .build()
+        .unwrap();
+    assert_eq!(cont.execute("let (c (:= (a b)))"), "");
+    assert_eq!(cont.execute("let (d (-> c))"), "");
+    assert_eq!(cont.execute("let (e (:= (f c)))"), "");
+    assert_eq!(cont.execute("delete* a"), "");
+    assert_eq!(cont.execute("f used_in"), "");
+    assert_eq!(cont.execute("let (c (:= (a b)))"), "");
+    assert_eq!(cont.execute("let (d (-> c))"), "");
+    assert_eq!(cont.execute("let (e (:= (f c)))"), "");
+}
+#[test]
+fn cascading_delete_keeps_used_concepts() {
+    let mut cont = common::context_with(Feature::Deletion);
+    assert_eq!(cont.execute("let (c (:= (a b)))"), "");
+    assert_eq!(cont.execute("let (d (:= (b e)))"), "");
+    assert_eq!(cont.execute("delete* a"), "");
+    assert_eq!(cont.execute("(label_of (d :=)) ->"), "b e");
+}
+#[test]
+fn delete_concrete_concept() {
+    let mut cont = common::context_with(Feature::Deletion);
+    assert_eq!(
+        cont.execute_structured("delete let"),
+        Err(ZiaError::DeletingConcrete)
+    );
+    assert_eq!(
+        cont.execute_structured("delete* let"),
+        Err(ZiaError::DeletingConcrete)
+    );
+}
+#[test]
+fn delete_fresh_symbol() {
+    let mut cont = common::context_with(Feature::Deletion);
+    assert_eq!(
+        cont.execute_structured("delete a"),
+        Err(ZiaError::RedundantDeletion)
+    );
+}
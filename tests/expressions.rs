@@ -0,0 +1,64 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate zia;
+
+use zia::{Context, ContextMaker, Execute, Expr, ZiaError};
+
+fn reduction_rule(left: &str, right: &str) -> Expr {
+    Expr::symbol("let").pair(Expr::symbol(left).pair(Expr::symbol("->").pair(Expr::symbol(right))))
+}
+fn label_of_reduction(symbol: &str) -> Expr {
+    Expr::symbol("label_of")
+        .pair(Expr::symbol(symbol).pair(Expr::symbol("->")))
+        .pair(Expr::symbol("->"))
+}
+#[test]
+fn display() {
+    assert_eq!(reduction_rule("a", "b").to_string(), "let (a (-> b))");
+    assert_eq!(label_of_reduction("a").to_string(), "(label_of (a ->)) ->");
+}
+#[test]
+fn reduction() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute_ast(&reduction_rule("a", "b")), "");
+    assert_eq!(cont.execute_ast(&label_of_reduction("a")), "b");
+    assert_eq!(cont.execute("(label_of (a ->)) ->"), "b");
+}
+#[test]
+fn same_errors_as_strings() {
+    let mut cont = Context::new();
+    assert_eq!(
+        cont.execute_ast(&Expr::symbol("a")),
+        ZiaError::NotAProgram.to_string()
+    );
+}
+#[test]
+fn invalid_symbols() {
+    let mut cont = Context::new();
+    assert_eq!(
+        cont.execute_ast(&reduction_rule("a b", "c")),
+        ZiaError::InvalidSymbol.to_string()
+    );
+    assert_eq!(
+        cont.execute_ast(&reduction_rule("a", "(b")),
+        ZiaError::InvalidSymbol.to_string()
+    );
+    assert_eq!(
+        cont.execute_ast(&Expr::symbol("")),
+        ZiaError::InvalidSymbol.to_string()
+    );
+}
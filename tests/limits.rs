@@ -0,0 +1,108 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate zia;
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use zia::{Context, ContextBuilder, ContextMaker, Execute, Feature, SetLimits, Steps, ZiaError};
+
+fn chain(cont: &mut Context) {
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("let (b (-> c))"), "");
+    assert_eq!(cont.execute("let (c (-> d))"), "");
+}
+
+#[test]
+fn within_limit() {
+    let mut cont = ContextBuilder::new().step_limit(3).build().unwrap();
+    chain(&mut cont);
+    assert_eq!(cont.execute("a ->*"), "d");
+    assert_eq!(cont.execute("a ->*"), "d");
+}
+#[test]
+fn exceeding_limit() {
+    let mut cont = ContextBuilder::new().step_limit(1).build().unwrap();
+    chain(&mut cont);
+    assert_eq!(
+        cont.execute_structured("a ->*"),
+        Err(ZiaError::ReductionLimitExceeded {
+            steps: 1,
+            expression: "b".to_string()
+        })
+    );
+    assert_eq!(
+        cont.reduce_expression("a", Steps::Finite(3)),
+        Err(ZiaError::ReductionLimitExceeded {
+            steps: 1,
+            expression: "b".to_string()
+        })
+    );
+    assert_eq!(cont.execute("a ->"), "b");
+}
+#[test]
+fn exceeding_limit_in_operand_of_equals() {
+    let mut cont = ContextBuilder::new()
+        .feature(Feature::Booleans)
+        .step_limit(2)
+        .build()
+        .unwrap();
+    chain(&mut cont);
+    assert_eq!(
+        cont.execute_structured("(a (= d)) ->"),
+        Err(ZiaError::ReductionLimitExceeded {
+            steps: 2,
+            expression: "c".to_string()
+        })
+    );
+}
+#[test]
+fn expansion_limit() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (c (:= (a b)))"), "");
+    assert_eq!(cont.execute("let (d (:= (c c)))"), "");
+    cont.set_step_limit(Some(2));
+    assert_eq!(
+        cont.execute_structured("(label_of (d :=)) ->"),
+        Err(ZiaError::ReductionLimitExceeded {
+            steps: 2,
+            expression: "c".to_string()
+        })
+    );
+    cont.set_step_limit(None);
+    assert_eq!(cont.execute("(label_of (d :=)) ->"), "(a b) (a b)");
+}
+#[test]
+fn cancellation() {
+    let flag = Arc::new(AtomicBool::new(false));
+    let mut cont = ContextBuilder::new()
+        .cancellation_flag(flag.clone())
+        .build()
+        .unwrap();
+    chain(&mut cont);
+    flag.store(true, Ordering::Relaxed);
+    assert_eq!(
+        cont.execute_structured("a ->*"),
+        Err(ZiaError::ReductionLimitExceeded {
+            steps: 0,
+            expression: "a".to_string()
+        })
+    );
+    flag.store(false, Ordering::Relaxed);
+    assert_eq!(cont.execute("a ->*"), "d");
+}
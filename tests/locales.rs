@@ -0,0 +1,151 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate zia;
+
+mod common;
+
+use zia::{Context, ContextMaker, Execute, Feature, Locale, Multilingual, ZiaError};
+
+#[test]
+fn english_by_default() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(
+        cont.execute("let (a (-> b))"),
+        "That reduction rule already exists."
+    );
+}
+#[test]
+fn french_labels() {
+    let mut cont = Context::with_locale(Locale::French);
+    assert_eq!(cont.execute("soit (a (-> b))"), "");
+    assert_eq!(cont.execute("(étiquette_de (a ->)) ->"), "b");
+    assert_eq!(
+        cont.execute("let (c (-> d))"),
+        Locale::French.describe(&ZiaError::NotAProgram)
+    );
+}
+#[test]
+fn builtin_labels() {
+    assert_eq!(Locale::English.builtin_label(0), Some("label_of"));
+    assert_eq!(Locale::French.builtin_label(0), Some("étiquette_de"));
+    assert_eq!(Locale::English.builtin_label(1000), None);
+}
+#[test]
+fn french_errors() {
+    let mut cont = Context::with_locale(Locale::French);
+    assert_eq!(cont.execute("soit (a (-> b))"), "");
+    assert_eq!(
+        cont.execute("soit (b (-> a))"),
+        "Une chaîne de règles de réduction ne peut pas boucler : b -> a -> b."
+    );
+    assert_eq!(
+        cont.execute("soit (b (-> a))"),
+        Locale::French.describe(&ZiaError::CyclicReduction {
+            chain: vec!["b".to_string(), "a".to_string(), "b".to_string()]
+        })
+    );
+}
+#[test]
+fn labels_in_active_language() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (not (-> a))"), "");
+    assert_eq!(cont.label_in(Locale::French, "not", "non"), Ok(()));
+    assert_eq!(cont.label_in(Locale::French, "a", "un"), Ok(()));
+    assert_eq!(cont.execute("non ->"), "non");
+    cont.set_language(Locale::French);
+    assert_eq!(cont.execute("non ->"), "un");
+    assert_eq!(cont.execute("not ->"), "un");
+    cont.set_language(Locale::English);
+    assert_eq!(cont.execute("not ->"), "a");
+}
+#[test]
+fn fallback_languages() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (not (-> a))"), "");
+    assert_eq!(cont.label_in(Locale::French, "not", "non"), Ok(()));
+    assert_eq!(cont.label_in(Locale::French, "a", "un"), Ok(()));
+    cont.set_fallback_languages(vec![Locale::French]);
+    assert_eq!(cont.execute("non ->"), "un");
+    assert_eq!(cont.label_in(Locale::English, "un", "one"), Ok(()));
+    assert_eq!(cont.execute("non ->"), "one");
+}
+#[test]
+fn replacing_and_removing_labels_in_language() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (not (-> a))"), "");
+    assert_eq!(cont.label_in(Locale::French, "a", "un"), Ok(()));
+    assert_eq!(cont.label_in(Locale::French, "a", "une"), Ok(()));
+    cont.set_language(Locale::French);
+    assert_eq!(cont.execute("not ->"), "une");
+    assert_eq!(cont.unlabel_in(Locale::French, "une"), Ok(()));
+    assert_eq!(cont.execute("not ->"), "a");
+    assert_eq!(
+        cont.unlabel_in(Locale::French, "a"),
+        Err(ZiaError::RedundantDeletion)
+    );
+}
+#[test]
+fn invalid_labels_in_language() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (not (-> a))"), "");
+    assert_eq!(
+        cont.label_in(Locale::French, "b", "bé"),
+        Err(ZiaError::UnknownSymbol {
+            symbol: "b".to_string(),
+            suggestions: Vec::new()
+        })
+    );
+    assert_eq!(
+        cont.label_in(Locale::French, "a", "u n"),
+        Err(ZiaError::InvalidSymbol)
+    );
+    assert_eq!(cont.label_in(Locale::French, "a", "un"), Ok(()));
+    assert_eq!(
+        cont.label_in(Locale::French, "a", "un"),
+        Err(ZiaError::RedundantTranslation)
+    );
+    assert_eq!(
+        cont.label_in(Locale::French, "not", "un"),
+        Err(ZiaError::DefinitionCollision {
+            symbol: "un".to_string(),
+            expression: "not".to_string()
+        })
+    );
+}
+#[test]
+fn labels_in_language_colliding_with_labels() {
+    let mut cont = common::context_with(Feature::Aliases);
+    assert_eq!(cont.execute("let (not (-> a))"), "");
+    assert_eq!(cont.execute("let (n (alias_of not))"), "");
+    assert_eq!(
+        cont.label_in(Locale::French, "not", "a"),
+        Err(ZiaError::DefinitionCollision {
+            symbol: "a".to_string(),
+            expression: "not".to_string()
+        })
+    );
+    assert_eq!(
+        cont.label_in(Locale::French, "a", "n"),
+        Err(ZiaError::DefinitionCollision {
+            symbol: "n".to_string(),
+            expression: "a".to_string()
+        })
+    );
+    assert_eq!(cont.label_in(Locale::French, "not", "n"), Ok(()));
+    assert_eq!(cont.label_in(Locale::French, "a", "a"), Ok(()));
+}
@@ -0,0 +1,143 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate zia;
+
+mod common;
+
+use zia::{Execute, Feature, ZiaError};
+
+#[test]
+fn modules_with_same_symbols() {
+    let mut cont = common::context_with(Feature::Modules);
+    assert_eq!(cont.execute("module logic"), "");
+    assert_eq!(cont.execute("let (not (-> a))"), "");
+    assert_eq!(cont.execute("module other"), "");
+    assert_eq!(cont.execute("let (not (-> b))"), "");
+    assert_eq!(cont.execute("module ::"), "");
+    assert_eq!(cont.execute("logic::not ->"), "logic::a");
+    assert_eq!(cont.execute("other::not ->"), "other::b");
+}
+#[test]
+fn unqualified_within_module() {
+    let mut cont = common::context_with(Feature::Modules);
+    assert_eq!(cont.execute("module logic"), "");
+    assert_eq!(cont.execute("let (not (-> a))"), "");
+    assert_eq!(cont.execute("not ->"), "a");
+}
+#[test]
+fn open_module() {
+    let mut cont = common::context_with(Feature::Modules);
+    assert_eq!(cont.execute("module logic"), "");
+    assert_eq!(cont.execute("let (not (-> a))"), "");
+    assert_eq!(cont.execute("module ::"), "");
+    assert_eq!(cont.execute("not ->"), "not");
+    assert_eq!(cont.execute("open logic"), "");
+    assert_eq!(cont.execute("not ->"), "a");
+}
+#[test]
+fn qualified_only_when_ambiguous() {
+    let mut cont = common::context_with(Feature::Modules);
+    assert_eq!(cont.execute("module logic"), "");
+    assert_eq!(cont.execute("let (not (-> a))"), "");
+    assert_eq!(cont.execute("module other"), "");
+    assert_eq!(cont.execute("let (not (-> b))"), "");
+    assert_eq!(cont.execute("module ::"), "");
+    assert_eq!(cont.execute("open logic"), "");
+    assert_eq!(cont.execute("open other"), "");
+    assert_eq!(cont.execute("not ->"), "a");
+    assert_eq!(cont.execute("let (x (-> logic::not))"), "");
+    assert_eq!(cont.execute("let (y (-> other::not))"), "");
+    assert_eq!(cont.execute("x ->"), "not");
+    assert_eq!(cont.execute("y ->"), "other::not");
+}
+#[test]
+fn global_labels_within_module() {
+    let mut cont = common::context_with(Feature::Modules);
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("module logic"), "");
+    assert_eq!(cont.execute("a ->"), "b");
+    assert_eq!(cont.execute("let (c (-> a))"), "");
+    assert_eq!(cont.execute("module ::"), "");
+    assert_eq!(cont.execute("logic::c ->"), "a");
+}
+#[test]
+fn redefining_symbol_within_module() {
+    let mut cont = common::context_with(Feature::Modules);
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("module a"), "");
+    assert_eq!(cont.execute("let (not (-> x))"), "");
+    assert_eq!(cont.execute("open a"), "");
+    assert_eq!(cont.execute("module b"), "");
+    assert_eq!(cont.execute("let (not (-> y))"), "");
+    assert_eq!(cont.execute("let (a (-> c))"), "");
+    assert_eq!(cont.execute("module ::"), "");
+    assert_eq!(cont.execute("a::not ->"), "x");
+    assert_eq!(cont.execute("b::not ->"), "b::y");
+    assert_eq!(cont.execute("a ->"), "b");
+    assert_eq!(cont.execute("b::a ->"), "b::c");
+    assert_eq!(cont.execute("module a"), "");
+    assert_eq!(cont.execute("let (a::not (-> z))"), "");
+    assert_eq!(cont.execute("not ->"), "z");
+}
+#[test]
+fn pattern_variables_within_module() {
+    let mut cont = common::context_with(Feature::Modules);
+    assert_eq!(cont.execute("module logic"), "");
+    assert_eq!(cont.execute("let ((not (not _x)) (-> _x))"), "");
+    assert_eq!(cont.execute("(not (not c)) ->"), "c");
+}
+#[test]
+fn built_in_symbols_within_module() {
+    let mut cont = common::context_with(Feature::Modules);
+    assert_eq!(cont.execute("module logic"), "");
+    assert_eq!(
+        cont.execute("let (let (-> x))"),
+        ZiaError::ConcreteReduction.to_string()
+    );
+    assert_eq!(
+        cont.execute("let (-> (-> x))"),
+        ZiaError::ConcreteReduction.to_string()
+    );
+    assert_eq!(
+        cont.execute("let (module (:= x))"),
+        ZiaError::DefinitionCollision {
+            symbol: "module".to_string(),
+            expression: "x".to_string()
+        }
+        .to_string()
+    );
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("a ->"), "b");
+    assert_eq!(cont.execute("module ::"), "");
+    assert_eq!(cont.execute("logic::a ->"), "logic::b");
+}
+#[test]
+fn global_symbols_within_module() {
+    let mut cont = common::context_with(Feature::Modules);
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("module logic"), "");
+    assert_eq!(cont.execute("let (::a (-> c))"), "");
+    assert_eq!(cont.execute("let (::d (-> a))"), "");
+    assert_eq!(cont.execute("::a ->"), "c");
+    assert_eq!(cont.execute("let (a (-> e))"), "");
+    assert_eq!(cont.execute("a ->"), "e");
+    assert_eq!(cont.execute("::a ->"), "c");
+    assert_eq!(cont.execute("module ::"), "");
+    assert_eq!(cont.execute("a ->"), "logic::c");
+    assert_eq!(cont.execute("d ->"), "a");
+    assert_eq!(cont.execute("logic::a ->"), "logic::e");
+}
@@ -0,0 +1,150 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate zia;
+
+mod common;
+
+use std::{cell::Cell, rc::Rc};
+use zia::{
+    Context, ContextMaker, Execute, Expr, Feature, Output, RegisterNative, Steps, ZiaError,
+};
+
+#[test]
+fn without_arguments() {
+    let mut cont = Context::new();
+    cont.register_native("now", |_, _| Ok(Some(Expr::symbol("noon"))))
+        .unwrap();
+    assert_eq!(cont.execute("now ->"), "noon");
+    assert_eq!(cont.execute("(label_of (now ->)) ->"), "noon");
+}
+#[test]
+fn with_arguments() {
+    let mut cont = Context::new();
+    cont.register_native("swap", |cont: &Context, arguments| {
+        let displayed: Vec<String> = arguments
+            .iter()
+            .filter_map(|&a| cont.display_concept(a))
+            .collect();
+        Ok(match displayed.len() {
+            2 => Some(Expr::symbol(&displayed[1]).pair(Expr::symbol(&displayed[0]))),
+            _ => None,
+        })
+    })
+    .unwrap();
+    assert_eq!(cont.execute("let (c (:= (a b)))"), "");
+    assert_eq!(cont.execute("((swap a) b) ->"), "b a");
+    assert_eq!(cont.execute("(swap a) ->"), "swap a");
+}
+#[test]
+fn unknown_and_composite_arguments() {
+    let mut cont = Context::new();
+    cont.register_native("f", |_, _| Ok(Some(Expr::symbol("n"))))
+        .unwrap();
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("let (q (-> b))"), "");
+    assert_eq!(cont.execute("(f zz) ->"), "f zz");
+    assert_eq!(cont.execute("(f (a q)) ->"), "f (b b)");
+    assert_eq!(cont.execute("let (c (:= (a q)))"), "");
+    assert_eq!(cont.execute("(f (a q)) ->"), "n");
+    assert_eq!(cont.execute("(f c) ->"), "n");
+}
+#[test]
+fn only_at_head() {
+    let mut cont = Context::new();
+    cont.register_native("f", |_, _| Ok(Some(Expr::symbol("n"))))
+        .unwrap();
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("(a f) ->"), "b f");
+    assert_eq!(cont.execute("(a (f a)) ->"), "b n");
+}
+#[test]
+fn errors() {
+    let mut cont = Context::new();
+    cont.register_native("fail", |_, _| Err(ZiaError::NotAProgram))
+        .unwrap();
+    assert_eq!(
+        cont.execute_structured("fail ->"),
+        Err(ZiaError::NotAProgram)
+    );
+}
+#[test]
+fn errors_stop_reduction() {
+    let mut cont = Context::new();
+    let calls = Rc::new(Cell::new(0));
+    let counter = calls.clone();
+    cont.register_native("boom", move |_, _| {
+        counter.set(counter.get() + 1);
+        Err(ZiaError::NotAProgram)
+    })
+    .unwrap();
+    assert_eq!(cont.execute("let (q (-> r))"), "");
+    assert_eq!(
+        cont.execute_structured("((boom q) (boom q)) ->"),
+        Err(ZiaError::NotAProgram)
+    );
+    assert_eq!(calls.get(), 1);
+    assert_eq!(
+        cont.reduce_expression("(boom q) r", Steps::NormalForm),
+        Err(ZiaError::NotAProgram)
+    );
+    assert_eq!(calls.get(), 2);
+}
+#[test]
+fn used_labels() {
+    let mut cont = common::context_with(Feature::Aliases);
+    assert_eq!(
+        cont.register_native("let", |_, _| Ok(None)),
+        Err(ZiaError::UsedLabel {
+            label: "let".to_string(),
+            concept: "let".to_string()
+        })
+    );
+    assert_eq!(cont.execute("let (c (alias_of (a b)))"), "");
+    assert_eq!(
+        cont.register_native("c", |_, _| Ok(None)),
+        Err(ZiaError::UsedLabel {
+            label: "c".to_string(),
+            concept: "a b".to_string()
+        })
+    );
+    assert_eq!(
+        cont.register_native("a b", |_, _| Ok(None)),
+        Err(ZiaError::InvalidSymbol)
+    );
+}
+#[test]
+fn concrete() {
+    let mut cont = Context::new();
+    cont.register_native("now", |_, _| Ok(None)).unwrap();
+    assert_eq!(
+        cont.execute("let (now (-> a))"),
+        ZiaError::ConcreteReduction.to_string()
+    );
+}
+#[test]
+fn display_missing_concepts() {
+    let mut cont = common::context_with(Feature::Deletion);
+    let created = match cont.execute_structured("let (a (-> b))") {
+        Ok(Output::Mutation(changes)) => changes.created,
+        _ => panic!("Expected a mutation"),
+    };
+    assert_eq!(cont.execute("delete a"), "");
+    for concept in created {
+        assert_eq!(cont.display_concept(concept), None);
+    }
+    assert_eq!(cont.display_concept(usize::max_value()), None);
+}
@@ -0,0 +1,66 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate zia;
+
+use zia::{Context, ContextMaker, Execute, Steps};
+
+fn chain() -> Context {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("let (b (-> c))"), "");
+    assert_eq!(cont.execute("let (c (-> d))"), "");
+    cont
+}
+
+#[test]
+fn single_step() {
+    let mut cont = chain();
+    assert_eq!(cont.execute("a ->"), "b");
+    assert_eq!(cont.execute("(label_of (a ->)) ->"), "b");
+}
+#[test]
+fn normal_form() {
+    let mut cont = chain();
+    assert_eq!(cont.execute("a ->*"), "d");
+    assert_eq!(cont.execute("(a e) ->*"), "d e");
+    assert_eq!(cont.execute("(label_of (a ->*)) ->"), "d");
+    assert_eq!(cont.execute("d ->*"), "d");
+}
+#[test]
+fn n_steps() {
+    let mut cont = chain();
+    assert_eq!(cont.execute("a (-> 2)"), "c");
+    assert_eq!(cont.execute("a (-> 0)"), "a");
+    assert_eq!(cont.execute("a (-> 5)"), "d");
+    assert_eq!(cont.execute("(label_of (a (-> 2))) ->"), "c");
+}
+#[test]
+fn api() {
+    let cont = chain();
+    assert_eq!(
+        cont.reduce_expression("a", Steps::Finite(1)),
+        Ok("b".to_string())
+    );
+    assert_eq!(
+        cont.reduce_expression("a", Steps::Finite(2)),
+        Ok("c".to_string())
+    );
+    assert_eq!(
+        cont.reduce_expression("a e", Steps::NormalForm),
+        Ok("d e".to_string())
+    );
+}
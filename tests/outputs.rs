@@ -0,0 +1,173 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate zia;
+
+use zia::{Context, ContextBuilder, ContextMaker, Execute, Feature, Output, WriteSink, ZiaError};
+
+#[test]
+fn reduction_rule_creates_concepts() {
+    let mut cont = Context::new();
+    match cont.execute_structured("let (a (-> b))") {
+        Ok(Output::Mutation(changes)) => {
+            assert!(!changes.created.is_empty());
+            assert!(changes.removed.is_empty());
+        }
+        _ => panic!("Expected a mutation"),
+    }
+}
+#[test]
+fn changing_reduction_rule_changes_concepts() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    match cont.execute_structured("let (a (-> b))") {
+        Err(ZiaError::RedundantReduction) => (),
+        _ => panic!("Expected a redundant reduction"),
+    };
+    match cont.execute_structured("let (a (-> a))") {
+        Ok(Output::Mutation(changes)) => {
+            assert!(changes.created.is_empty());
+            assert!(!changes.changed.is_empty());
+        }
+        _ => panic!("Expected a mutation"),
+    }
+}
+#[test]
+fn removing_definition_removes_concepts() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (a (:= (b c)))"), "");
+    match cont.execute_structured("let (a (:= a))") {
+        Ok(Output::Mutation(changes)) => assert!(!changes.removed.is_empty()),
+        _ => panic!("Expected a mutation"),
+    }
+}
+#[test]
+fn printed_label() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(
+        cont.execute_structured("(label_of (a ->)) ->"),
+        Ok(Output::Printed("b".to_string()))
+    );
+}
+#[test]
+fn value_of_reduction() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(
+        cont.execute_structured("a ->"),
+        Ok(Output::Value("b".to_string()))
+    );
+    assert_eq!(cont.execute("a ->"), "b");
+}
+#[test]
+fn value_without_reduction() {
+    let mut cont = Context::new();
+    assert_eq!(
+        cont.execute_structured("a ->"),
+        Ok(Output::Value("a".to_string()))
+    );
+    assert_eq!(cont.execute("a ->"), "a");
+}
+#[test]
+fn query_without_results() {
+    let mut cont = ContextBuilder::new()
+        .feature(Feature::Introspection)
+        .build()
+        .unwrap();
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    for command in &["a <-", "a used_in", "search zz*", "trace b"] {
+        assert_eq!(
+            cont.execute_structured(command),
+            Ok(Output::Printed(String::new()))
+        );
+    }
+    assert_eq!(cont.execute("a <-"), "");
+}
+#[test]
+fn not_a_program() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute_structured("a"), Err(ZiaError::NotAProgram));
+}
+#[test]
+fn error_codes() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    let error = cont.execute_structured("let (b (-> a))").unwrap_err();
+    assert_eq!(error.code(), "Z0007");
+    assert_eq!(
+        error.to_string(),
+        "Cannot allow a chain of reduction rules to loop: b -> a -> b."
+    );
+}
+#[test]
+fn outputs_written_to_sink() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    let mut outputs = Vec::<Output>::new();
+    assert_eq!(
+        cont.execute_to("a ->", &mut outputs),
+        Ok(Default::default())
+    );
+    assert_eq!(outputs, vec![Output::Value("b".to_string())]);
+}
+#[test]
+fn mutation_writes_nothing_to_sink() {
+    let mut cont = Context::new();
+    let mut outputs = Vec::<String>::new();
+    let changes = cont.execute_to("let (a (-> b))", &mut outputs).unwrap();
+    assert!(!changes.created.is_empty());
+    assert!(outputs.is_empty());
+}
+#[test]
+fn command_with_several_outputs() {
+    let mut cont = ContextBuilder::new()
+        .feature(Feature::Introspection)
+        .build()
+        .unwrap();
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("let (b (-> c))"), "");
+    let mut outputs = Vec::<String>::new();
+    assert!(cont.execute_to("trace (a d)", &mut outputs).is_ok());
+    assert_eq!(outputs, vec!["a -> b (a)", "b -> c (b)"]);
+}
+#[test]
+fn error_writes_nothing_to_sink() {
+    let mut cont = Context::new();
+    let mut outputs = Vec::<String>::new();
+    assert_eq!(
+        cont.execute_to("a", &mut outputs),
+        Err(ZiaError::NotAProgram)
+    );
+    assert!(outputs.is_empty());
+}
+#[test]
+fn outputs_written_to_writer() {
+    let mut cont = ContextBuilder::new()
+        .feature(Feature::Introspection)
+        .build()
+        .unwrap();
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("let (b (-> c))"), "");
+    let mut sink = WriteSink::new(Vec::<u8>::new());
+    assert!(cont.execute_to("trace (a d)", &mut sink).is_ok());
+    assert!(cont.execute_to("a ->", &mut sink).is_ok());
+    assert!(sink.take_error().is_none());
+    assert_eq!(
+        String::from_utf8(sink.into_inner()).unwrap(),
+        "a -> b (a)\nb -> c (b)\nb\n"
+    );
+}
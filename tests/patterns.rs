@@ -0,0 +1,152 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate zia;
+
+mod common;
+
+use zia::{Context, ContextMaker, Execute, Feature, ReductionStep, ZiaError};
+
+#[test]
+fn double_negation() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let ((not (not _x)) (-> _x))"), "");
+    assert_eq!(cont.execute("(not (not a)) ->"), "a");
+    assert_eq!(cont.execute("(not (not (b c))) ->"), "b c");
+    assert_eq!(cont.execute("(not a) ->"), "not a");
+}
+#[test]
+fn nested_instance() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let ((not (not _x)) (-> _x))"), "");
+    assert_eq!(cont.execute("(b (not (not a))) ->"), "b a");
+    assert_eq!(cont.execute("(not (not (not (not a)))) ->*"), "a");
+}
+#[test]
+fn consistent_binding() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let ((same (_x _x)) (-> yes))"), "");
+    assert_eq!(cont.execute("(same (a a)) ->"), "yes");
+    assert_eq!(cont.execute("(same (a b)) ->"), "same (a b)");
+}
+#[test]
+fn ground_rule_first() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let ((f _x) (-> g))"), "");
+    assert_eq!(cont.execute("let ((f a) (-> h))"), "");
+    assert_eq!(cont.execute("(f a) ->"), "h");
+    assert_eq!(cont.execute("(f b) ->"), "g");
+}
+#[test]
+fn pattern_before_components() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("let ((f _x) (-> (_x _x)))"), "");
+    assert_eq!(cont.execute("(f a) ->"), "a a");
+    assert_eq!(cont.execute("(f a) ->*"), "b b");
+}
+#[test]
+fn rules_are_concepts() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let ((not (not _x)) (-> _x))"), "");
+    assert_eq!(cont.execute("(label_of ((not (not _x)) ->)) ->"), "_x");
+    assert_eq!(cont.execute("let ((not (not _x)) (-> (not (not _x))))"), "");
+    assert_eq!(cont.execute("(not (not a)) ->"), "not (not a)");
+}
+#[test]
+fn unbound_variable() {
+    let mut cont = Context::new();
+    assert_eq!(
+        cont.execute_structured("let ((f _x) (-> _y))"),
+        Err(ZiaError::UnboundVariable {
+            variable: "_y".to_string(),
+            pattern: "f _x".to_string()
+        })
+    );
+}
+#[test]
+fn expanding_pattern() {
+    let mut cont = Context::new();
+    assert_eq!(
+        cont.execute_structured("let ((f _x) (-> (f (g _x))))"),
+        Err(ZiaError::ExpandingReduction {
+            concept: "f _x".to_string(),
+            reduction: "f (g _x)".to_string()
+        })
+    );
+    assert_eq!(
+        cont.execute_structured("let (_y (-> a))"),
+        Err(ZiaError::ExpandingReduction {
+            concept: "_y".to_string(),
+            reduction: "a".to_string()
+        })
+    );
+}
+#[test]
+fn traced() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let ((not (not _x)) (-> _x))"), "");
+    assert_eq!(
+        cont.trace_reduction("not (not a)"),
+        Ok(vec![ReductionStep {
+            before: "not (not a)".to_string(),
+            rule: "not (not _x)".to_string(),
+            after: "a".to_string(),
+        }])
+    );
+}
+#[test]
+fn cycle_of_pattern_rules() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let ((f _x) (-> (g (h _x))))"), "");
+    assert_eq!(
+        cont.execute_structured("let ((g (h _y)) (-> (f _y)))"),
+        Err(ZiaError::CyclicReduction {
+            chain: vec![
+                "g (h _y)".to_string(),
+                "f _x".to_string(),
+                "g (h _y)".to_string()
+            ]
+        })
+    );
+    assert_eq!(cont.execute("(f a) ->*"), "g (h a)");
+}
+#[test]
+fn rules_are_tried_in_definition_order() {
+    let mut cont = common::context_with(Feature::Deletion);
+    assert_eq!(cont.execute("let (j1 (:= (j2 (j3 j4))))"), "");
+    assert_eq!(cont.execute("let ((f _x) (-> a))"), "");
+    assert_eq!(cont.execute("delete* j1"), "");
+    assert!(cont.execute_structured("let ((f (g _y)) (-> b))").is_ok());
+    assert_eq!(cont.execute("(f (g c)) ->"), "a");
+    assert_eq!(cont.execute("let ((f _x) (-> (f _x)))"), "");
+    assert_eq!(cont.execute("(f (g c)) ->"), "b");
+    assert_eq!(cont.execute("let ((f _x) (-> a))"), "");
+    assert_eq!(cont.execute("(f (g c)) ->"), "b");
+}
+#[test]
+fn cycle_through_a_ground_rule() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let ((f _x) (-> (g _x)))"), "");
+    assert_eq!(
+        cont.execute_structured("let ((g a) (-> (f a)))"),
+        Err(ZiaError::CyclicReduction {
+            chain: vec!["g a".to_string(), "f _x".to_string(), "g a".to_string()]
+        })
+    );
+    assert_eq!(cont.execute("(f b) ->*"), "g b");
+    assert_eq!(cont.execute("(f a) ->*"), "g a");
+}
@@ -0,0 +1,62 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate zia;
+
+mod common;
+
+use zia::{Execute, Feature, Output};
+
+#[test]
+fn direct_preimages() {
+    let mut cont = common::context_with(Feature::Introspection);
+    assert_eq!(cont.execute("let (c (-> b))"), "");
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("let (d (-> a))"), "");
+    assert_eq!(cont.execute("b <-"), "a\nc");
+}
+#[test]
+fn eventual_preimages() {
+    let mut cont = common::context_with(Feature::Introspection);
+    assert_eq!(cont.execute("let (c (-> b))"), "");
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("let (d (-> a))"), "");
+    assert_eq!(cont.execute("b <-*"), "a\nc\nd");
+    assert_eq!(cont.execute("a <-*"), "d");
+}
+#[test]
+fn composition_preimage() {
+    let mut cont = common::context_with(Feature::Introspection);
+    assert_eq!(cont.execute("let ((e f) (-> b))"), "");
+    assert_eq!(cont.execute("b <-"), "e f");
+}
+#[test]
+fn each_preimage_is_an_output() {
+    let mut cont = common::context_with(Feature::Introspection);
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("let (c (-> b))"), "");
+    let mut outputs = Vec::<String>::new();
+    assert!(cont.execute_to("b <-", &mut outputs).is_ok());
+    assert_eq!(outputs, vec!["a", "c"]);
+}
+#[test]
+fn no_preimages() {
+    let mut cont = common::context_with(Feature::Introspection);
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    let mut outputs = Vec::<Output>::new();
+    assert!(cont.execute_to("a <-", &mut outputs).is_ok());
+    assert!(outputs.is_empty());
+}
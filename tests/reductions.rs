@@ -50,7 +50,10 @@ fn cycle() {
     assert_eq!(cont.execute("let (a (-> b))"), "");
     assert_eq!(
         cont.execute("let (b (-> a))"),
-        ZiaError::CyclicReduction.to_string()
+        ZiaError::CyclicReduction {
+            chain: vec!["b".to_string(), "a".to_string(), "b".to_string()],
+        }
+        .to_string()
     );
     assert_eq!(cont.execute("(label_of (b ->))->"), "b");
 }
@@ -71,7 +74,11 @@ fn infinite_expansion() {
     let mut cont = Context::new();
     assert_eq!(
         cont.execute("let (b (-> (a b)))"),
-        ZiaError::ExpandingReduction.to_string()
+        ZiaError::ExpandingReduction {
+            concept: "b".to_string(),
+            reduction: "a b".to_string(),
+        }
+        .to_string()
     );
 }
 #[test]
@@ -126,3 +133,11 @@ fn reducing_concrete() {
         ZiaError::ConcreteReduction.to_string()
     );
 }
+#[test]
+fn new_symbol_on_both_sides() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let ((f a) (-> a))"), "");
+    assert_eq!(cont.execute("(label_of ((f a) ->)) ->"), "a");
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("(label_of ((f a) ->*)) ->"), "b");
+}
@@ -0,0 +1,118 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate zia;
+
+mod common;
+
+use zia::{Context, ContextBuilder, ContextMaker, Execute, Feature, ZiaError, ZiaWarning};
+
+#[test]
+fn previous_labels() {
+    let mut cont = common::context_with(Feature::LabelHistory);
+    assert_eq!(cont.execute("let (b (-> c))"), "");
+    assert_eq!(cont.execute("let (e (:= b))"), "");
+    assert_eq!(cont.execute("let (f (:= e))"), "");
+    assert_eq!(cont.execute("f previous_labels"), "b\ne");
+    assert_eq!(cont.execute("c previous_labels"), "");
+}
+#[test]
+fn relabelling_reducing_concepts() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (apple (-> fruit))"), "");
+    assert_eq!(cont.execute("let (pear (:= apple))"), "");
+    assert_eq!(cont.execute("pear ->"), "fruit");
+    assert_eq!(cont.execute("(label_of pear) ->"), "pear");
+    assert_eq!(cont.execute("let (pie (:= (baked pear)))"), "");
+    assert_eq!(cont.execute("pie ->"), "baked fruit");
+    assert_eq!(cont.execute("let (tart (:= pie))"), "");
+    assert_eq!(cont.execute("tart ->"), "baked fruit");
+    assert_eq!(cont.execute("(baked pear) ->"), "baked fruit");
+}
+#[test]
+fn reading_previous_label() {
+    let mut cont = common::context_with(Feature::LabelHistory);
+    assert_eq!(cont.execute("let (b (-> c))"), "");
+    assert_eq!(cont.execute("let (e (:= b))"), "");
+    assert_eq!(cont.execute("e ->"), "c");
+    assert_eq!(
+        cont.execute("b ->"),
+        format!(
+            "{}\nc",
+            ZiaWarning::RenamedLabel {
+                old: "b".to_string(),
+                new: "e".to_string()
+            }
+        )
+    );
+    assert_eq!(cont.execute("e ->"), "c");
+}
+#[test]
+fn warning_message() {
+    assert_eq!(
+        ZiaWarning::RenamedLabel {
+            old: "b".to_string(),
+            new: "e".to_string()
+        }
+        .to_string(),
+        "`b` was renamed to `e`."
+    );
+}
+#[test]
+fn forgetting_previous_labels() {
+    let mut cont = common::context_with(Feature::LabelHistory);
+    assert_eq!(cont.execute("let (b (-> c))"), "");
+    assert_eq!(cont.execute("let (e (:= b))"), "");
+    assert_eq!(cont.execute("forget_labels e"), "");
+    assert_eq!(cont.execute("e previous_labels"), "");
+    assert_eq!(cont.execute("b ->"), "b");
+    assert_eq!(
+        cont.execute("forget_labels e"),
+        ZiaError::RedundantDeletion.to_string()
+    );
+}
+#[test]
+fn previous_labels_of_deleted_concept() {
+    let mut cont = ContextBuilder::new()
+        .feature(Feature::LabelHistory)
+        .feature(Feature::Deletion)
+        .build()
+        .unwrap();
+    assert_eq!(cont.execute("let (b (-> c))"), "");
+    assert_eq!(cont.execute("let (e (:= b))"), "");
+    assert_eq!(cont.execute("delete e"), "");
+    assert_eq!(cont.execute("b ->"), "b");
+}
+#[test]
+fn renaming_back_and_forth() {
+    let mut cont = common::context_with(Feature::LabelHistory);
+    assert_eq!(cont.execute("let (c (:= (a b)))"), "");
+    assert_eq!(cont.execute("let (d (:= c))"), "");
+    assert_eq!(cont.execute("let (c (:= d))"), "");
+    assert_eq!(cont.execute("(label_of (c :=)) ->"), "a b");
+    assert_eq!(cont.execute("(label_of c) ->"), "c");
+    assert_eq!(cont.execute("c previous_labels"), "c\nd");
+    assert_eq!(cont.execute("let (d (:= c))"), "");
+    assert_eq!(cont.execute("(label_of (d :=)) ->"), "a b");
+}
+#[test]
+fn previous_labels_not_read_without_feature() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (b (-> c))"), "");
+    assert_eq!(cont.execute("let (e (:= b))"), "");
+    assert_eq!(cont.execute("b ->"), "b");
+    assert_eq!(cont.execute("e ->"), "c");
+}
@@ -0,0 +1,111 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate zia;
+
+mod common;
+
+use zia::{
+    ConceptKind, Context, ContextMaker, Execute, Feature, LabelSearch, SearchLabels, SetStrictness,
+    Strictness, ZiaError,
+};
+
+#[test]
+fn glob() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (apple (-> fruit))"), "");
+    assert_eq!(cont.execute("let (apply (-> fruit))"), "");
+    assert_eq!(
+        cont.search_labels(&LabelSearch::glob("app*")),
+        ["apple", "apply"]
+    );
+    assert_eq!(
+        cont.search_labels(&LabelSearch::glob("appl?")),
+        ["apple", "apply"]
+    );
+    assert_eq!(cont.search_labels(&LabelSearch::glob("*uit")), ["fruit"]);
+    assert!(cont.search_labels(&LabelSearch::glob("app")).is_empty());
+}
+#[test]
+fn regex() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (apple (-> fruit))"), "");
+    assert_eq!(cont.execute("let (apply (-> fruit))"), "");
+    assert_eq!(
+        cont.search_labels(&LabelSearch::regex("^app.e$")),
+        ["apple"]
+    );
+    assert_eq!(cont.search_labels(&LabelSearch::regex("ui")), ["fruit"]);
+}
+#[test]
+fn kinds() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (apple (-> fruit))"), "");
+    assert_eq!(cont.execute("let (pear (:= apple))"), "");
+    assert_eq!(
+        cont.search_labels(&LabelSearch::glob("->*").kind(ConceptKind::Concrete)),
+        ["->", "->*"]
+    );
+    assert!(cont
+        .search_labels(&LabelSearch::glob("->*").kind(ConceptKind::Abstract))
+        .is_empty());
+    assert!(cont.search_labels(&LabelSearch::glob("apple")).is_empty());
+    assert_eq!(
+        cont.search_labels(&LabelSearch::glob("apple").kind(ConceptKind::String)),
+        ["apple"]
+    );
+}
+#[test]
+fn definitions_and_reductions() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (apple (-> fruit))"), "");
+    assert_eq!(cont.execute("let (pie (:= (baked apple)))"), "");
+    assert_eq!(
+        cont.search_labels(&LabelSearch::glob("p*").with_definition(true)),
+        ["pie"]
+    );
+    assert_eq!(
+        cont.search_labels(&LabelSearch::glob("a*").with_definition(false)),
+        ["apple"]
+    );
+    assert_eq!(
+        cont.search_labels(
+            &LabelSearch::glob("*")
+                .kind(ConceptKind::Abstract)
+                .with_reduction(true)
+        ),
+        ["apple"]
+    );
+}
+#[test]
+fn search_command() {
+    let mut cont = common::context_with(Feature::Introspection);
+    assert_eq!(cont.execute("let (apple (-> fruit))"), "");
+    assert_eq!(cont.execute("let (apply (-> fruit))"), "");
+    assert_eq!(cont.execute("search app*"), "apple\napply");
+    assert_eq!(cont.execute("search b*"), "");
+    assert_eq!(
+        cont.execute("search (app* fruit)"),
+        ZiaError::NotAProgram.to_string()
+    );
+}
+#[test]
+fn search_command_in_strict_context() {
+    let mut cont = common::context_with(Feature::Introspection);
+    cont.set_strictness(Strictness::Queries);
+    assert_eq!(cont.execute("let (apple (-> fruit))"), "");
+    assert_eq!(cont.execute("search ap*"), "apple");
+}
@@ -0,0 +1,88 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate zia;
+
+use zia::{
+    Context, ContextBuilder, ContextMaker, Execute, SetStrictness, Strictness, SuggestLabels,
+    ZiaError,
+};
+
+#[test]
+fn closest_labels() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (apple (-> fruit))"), "");
+    assert_eq!(cont.execute("let (apply (-> fruit))"), "");
+    assert_eq!(cont.suggest_labels("appel"), ["apple", "apply"]);
+    assert_eq!(cont.suggest_labels("frut"), ["fruit"]);
+    assert!(cont.suggest_labels("banana").is_empty());
+}
+#[test]
+fn lenient_by_default() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (b (-> c))"), "");
+    assert_eq!(cont.execute("bb ->"), "bb");
+    assert_eq!(cont.execute("let (a (-> bb))"), "");
+}
+#[test]
+fn strict_queries() {
+    let mut cont = ContextBuilder::new()
+        .strictness(Strictness::Queries)
+        .build()
+        .unwrap();
+    assert_eq!(cont.execute("let (bar (-> baz))"), "");
+    assert_eq!(
+        cont.execute("bat ->"),
+        "`bat` isn't used. Did you mean `bar`, `baz`?"
+    );
+    assert_eq!(
+        cont.execute_structured("xyz ->"),
+        Err(ZiaError::UnknownSymbol {
+            symbol: "xyz".to_string(),
+            suggestions: vec![]
+        })
+    );
+    assert_eq!(cont.execute("bar (-> 2)"), "baz");
+    assert_eq!(cont.execute("let (bat (-> c))"), "");
+    assert_eq!(cont.execute("bat ->"), "c");
+}
+#[test]
+fn strict_commands() {
+    let mut cont = Context::new();
+    cont.set_strictness(Strictness::Commands);
+    assert_eq!(cont.execute("let (apple (-> c))"), "");
+    assert_eq!(
+        cont.execute_structured("let (a (-> aple))"),
+        Err(ZiaError::UnknownSymbol {
+            symbol: "aple".to_string(),
+            suggestions: vec!["apple".to_string()]
+        })
+    );
+    assert_eq!(cont.execute("let (a (-> d))"), "");
+    assert_eq!(cont.execute("let ((f _x) (-> _x))"), "");
+    assert_eq!(cont.execute("(f a) ->"), "a");
+    cont.set_strictness(Strictness::Lenient);
+    assert_eq!(cont.execute("let (e (-> aple))"), "");
+}
+#[test]
+fn short_symbols_similar_to_labels() {
+    let mut cont = Context::new();
+    cont.set_strictness(Strictness::Commands);
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("let (bb (-> c))"), "");
+    assert_eq!(cont.execute("let (ab (-> q))"), "");
+    assert_eq!(cont.execute("ab ->"), "q");
+}
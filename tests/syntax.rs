@@ -16,7 +16,7 @@
 */
 extern crate zia;
 
-use zia::{Context, ContextMaker, Execute, ZiaError};
+use zia::{Context, ContextMaker, Execute, Normalisation, SetNormalisation, ZiaError};
 
 #[test]
 fn empty_parentheses() {
@@ -31,3 +31,48 @@ fn ambiguous_expression() {
         ZiaError::AmbiguousExpression.to_string()
     );
 }
+#[test]
+fn tab_separated_symbols() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let\t(a\t(->\tb))"), "");
+    assert_eq!(cont.execute("(label_of (a ->)) ->"), "b");
+}
+#[test]
+fn canonically_equivalent_symbols() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (caf\u{e9} (-> b))"), "");
+    assert_eq!(cont.execute("(label_of (cafe\u{301} ->)) ->"), "b");
+}
+#[test]
+fn compatibility_equivalent_symbols() {
+    let mut cont = Context::new();
+    cont.set_normalisation(Normalisation::Nfkc).unwrap();
+    assert_eq!(cont.execute("let (\u{fb01} (-> b))"), "");
+    assert_eq!(cont.execute("(label_of (fi ->)) ->"), "b");
+}
+#[test]
+fn unnormalised_symbols() {
+    let mut cont = Context::new();
+    cont.set_normalisation(Normalisation::None).unwrap();
+    assert_eq!(cont.execute("let (caf\u{e9} (-> b))"), "");
+    assert_eq!(
+        cont.execute("(label_of (cafe\u{301} ->)) ->"),
+        "cafe\u{301}"
+    );
+}
+#[test]
+fn colliding_normalisation() {
+    let mut cont = Context::new();
+    cont.set_normalisation(Normalisation::None).unwrap();
+    assert_eq!(cont.execute("let (caf\u{e9} (-> b))"), "");
+    assert_eq!(cont.execute("let (cafe\u{301} (-> c))"), "");
+    assert_eq!(
+        cont.set_normalisation(Normalisation::Nfc),
+        Err(ZiaError::NormalisationCollision {
+            first: "cafe\u{301}".to_string(),
+            second: "caf\u{e9}".to_string(),
+        })
+    );
+    assert_eq!(cont.execute("(label_of (caf\u{e9} ->)) ->"), "b");
+    assert_eq!(cont.execute("(label_of (cafe\u{301} ->)) ->"), "c");
+}
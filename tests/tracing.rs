@@ -0,0 +1,56 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate zia;
+
+use zia::{Context, ContextBuilder, ContextMaker, Execute, Feature, ReductionStep};
+
+#[test]
+fn trace_steps() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("let (b (-> c))"), "");
+    assert_eq!(
+        cont.trace_reduction("a d"),
+        Ok(vec![
+            ReductionStep {
+                before: "a".to_string(),
+                rule: "a".to_string(),
+                after: "b".to_string(),
+            },
+            ReductionStep {
+                before: "b".to_string(),
+                rule: "b".to_string(),
+                after: "c".to_string(),
+            },
+        ])
+    );
+}
+#[test]
+fn normal_form_has_no_steps() {
+    let cont = Context::new();
+    assert_eq!(cont.trace_reduction("a b"), Ok(vec![]));
+}
+#[test]
+fn trace_command() {
+    let mut cont = ContextBuilder::new()
+        .feature(Feature::Introspection)
+        .build()
+        .unwrap();
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    assert_eq!(cont.execute("let (b (-> c))"), "");
+    assert_eq!(cont.execute("trace (a d)"), "a -> b (a)\nb -> c (b)");
+}
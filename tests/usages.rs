@@ -0,0 +1,52 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate zia;
+
+mod common;
+
+use zia::{Execute, Feature, Output};
+
+#[test]
+fn direct_usages() {
+    let mut cont = common::context_with(Feature::Introspection);
+    assert_eq!(cont.execute("let (c (:= (a b)))"), "");
+    assert_eq!(cont.execute("let ((a f) (-> g))"), "");
+    assert_eq!(cont.execute("a used_in"), "a f\nc");
+    assert_eq!(cont.execute("b used_in"), "c");
+}
+#[test]
+fn transitive_usages() {
+    let mut cont = common::context_with(Feature::Introspection);
+    assert_eq!(cont.execute("let (c (:= (a b)))"), "");
+    assert_eq!(cont.execute("let (d (:= (e c)))"), "");
+    assert_eq!(cont.execute("a used_in"), "c");
+    assert_eq!(cont.execute("a used_in*"), "c\nd");
+}
+#[test]
+fn unlabelled_composition_is_expanded() {
+    let mut cont = common::context_with(Feature::Introspection);
+    assert_eq!(cont.execute("let (((a b) c) (-> d))"), "");
+    assert_eq!(cont.execute("a used_in*"), "(a b) c\na b");
+}
+#[test]
+fn labels_are_not_usages() {
+    let mut cont = common::context_with(Feature::Introspection);
+    assert_eq!(cont.execute("let (a (-> b))"), "");
+    let mut outputs = Vec::<Output>::new();
+    assert!(cont.execute_to("a used_in", &mut outputs).is_ok());
+    assert!(outputs.is_empty());
+}
@@ -0,0 +1,151 @@
+/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+extern crate zia;
+
+mod common;
+
+use zia::{
+    Context, ContextBuilder, ContextMaker, Execute, Feature, Output, SetWarnings, WarningCategory,
+    ZiaWarning,
+};
+
+#[test]
+fn implicit_symbols_suppressed_by_default() {
+    let mut cont = Context::new();
+    let report = cont.execute_with_warnings("let (a (-> b))").unwrap();
+    assert_eq!(report.warnings, vec![]);
+}
+#[test]
+fn implicit_symbols() {
+    let mut cont = ContextBuilder::new()
+        .allow_warnings(WarningCategory::ImplicitSymbol)
+        .build()
+        .unwrap();
+    let report = cont.execute_with_warnings("let (a (-> b))").unwrap();
+    assert_eq!(
+        report.warnings,
+        vec![
+            ZiaWarning::ImplicitSymbol {
+                symbol: "a".to_string()
+            },
+            ZiaWarning::ImplicitSymbol {
+                symbol: "b".to_string()
+            }
+        ]
+    );
+    assert_eq!(
+        cont.execute("let (c (-> b))"),
+        "`c` wasn't used before so a new concept was made for it."
+    );
+    assert_eq!(
+        cont.execute("let ((f _x) (-> b))"),
+        "`f` wasn't used before so a new concept was made for it."
+    );
+}
+#[test]
+fn unreachable_rule() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let ((f _x) (-> g))"), "");
+    let report = cont
+        .execute_with_warnings("let ((f (h _y)) (-> i))")
+        .unwrap();
+    assert_eq!(
+        report.warnings,
+        vec![ZiaWarning::UnreachableRule {
+            rule: "f (h _y)".to_string(),
+            shadowing_rule: "f _x".to_string()
+        }]
+    );
+    assert_eq!(cont.execute("(f (h c)) ->"), "g");
+}
+#[test]
+fn unreachable_rule_after_deletion() {
+    let mut cont = common::context_with(Feature::Deletion);
+    assert_eq!(cont.execute("let (j1 (:= (j2 (j3 j4))))"), "");
+    assert_eq!(cont.execute("let ((f _x) (-> g))"), "");
+    assert_eq!(cont.execute("delete* j1"), "");
+    let report = cont
+        .execute_with_warnings("let ((f (h _y)) (-> i))")
+        .unwrap();
+    assert_eq!(
+        report.warnings,
+        vec![ZiaWarning::UnreachableRule {
+            rule: "f (h _y)".to_string(),
+            shadowing_rule: "f _x".to_string()
+        }]
+    );
+}
+#[test]
+fn reachable_rules() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let ((f (h _y)) (-> i))"), "");
+    assert_eq!(cont.execute("let ((f _x) (-> g))"), "");
+    assert_eq!(cont.execute("(f (h c)) ->"), "i");
+    assert_eq!(cont.execute("(f c) ->"), "g");
+}
+#[test]
+fn renamed_label() {
+    let mut cont = ContextBuilder::new()
+        .feature(Feature::LabelHistory)
+        .build()
+        .unwrap();
+    assert_eq!(cont.execute("let (b (-> c))"), "");
+    assert_eq!(cont.execute("let (e (:= b))"), "");
+    let report = cont.execute_with_warnings("b ->").unwrap();
+    assert_eq!(report.output, Output::Value("c".to_string()));
+    assert_eq!(
+        report.warnings,
+        vec![ZiaWarning::RenamedLabel {
+            old: "b".to_string(),
+            new: "e".to_string()
+        }]
+    );
+    assert_eq!(
+        cont.execute_structured("b ->"),
+        Ok(Output::Value("c".to_string()))
+    );
+}
+#[test]
+fn suppressing_warnings() {
+    let mut cont = ContextBuilder::new()
+        .feature(Feature::LabelHistory)
+        .suppress_warnings(WarningCategory::RenamedLabel)
+        .build()
+        .unwrap();
+    assert_eq!(cont.execute("let (b (-> c))"), "");
+    assert_eq!(cont.execute("let (e (:= b))"), "");
+    assert_eq!(cont.execute("b ->"), "c");
+    cont.allow_warnings(WarningCategory::RenamedLabel);
+    assert_eq!(cont.execute("b ->"), "`b` was renamed to `e`.\nc");
+    cont.suppress_warnings(WarningCategory::RenamedLabel);
+    assert_eq!(cont.execute("b ->"), "c");
+}
+#[test]
+fn warnings_written_to_sink() {
+    let mut cont = Context::new();
+    assert_eq!(cont.execute("let ((f _x) (-> g))"), "");
+    let mut outputs = Vec::<String>::new();
+    assert!(cont
+        .execute_to("let ((f (h _y)) (-> i))", &mut outputs)
+        .is_ok());
+    assert_eq!(
+        outputs,
+        vec![
+            "The reduction rule of `f (h _y)` will never be used because `f _x` is matched first."
+        ]
+    );
+}
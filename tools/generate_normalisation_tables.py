@@ -0,0 +1,116 @@
+#!/usr/bin/env python3
+"""Generates src/normalising/tables.rs from the Unicode Character Database bundled with Python.
+
+The version of the database is that of `unicodedata.unidata_version`, which is recorded in the
+header of the generated file. Run from the root of the repository:
+
+    python3 tools/generate_normalisation_tables.py > src/normalising/tables.rs
+    rustfmt --edition 2015 src/normalising/tables.rs
+"""
+
+import sys
+import unicodedata
+
+HEADER = """/*  Library for the Zia programming language.
+    Copyright (C) 2018  Charles Johnson
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU General Public License for more details.
+
+    You should have received a copy of the GNU General Public License
+    along with this program. If not, see <http://www.gnu.org/licenses/>.
+*/
+
+// Generated by tools/generate_normalisation_tables.py from version {version} of the Unicode Character
+// Database. Hangul syllables are left out because they are decomposed and composed algorithmically.
+"""
+
+
+def literal(c):
+    return "'\\u{%X}'" % ord(c)
+
+
+def characters():
+    for code in range(sys.maxunicode + 1):
+        if not 0xD800 <= code <= 0xDFFF:
+            yield chr(code)
+
+
+def combining_class_ranges():
+    ranges = []
+    for c in characters():
+        combining_class = unicodedata.combining(c)
+        if combining_class == 0:
+            continue
+        if ranges and ord(ranges[-1][1]) + 1 == ord(c) and ranges[-1][2] == combining_class:
+            ranges[-1][1] = c
+        else:
+            ranges.append([c, c, combining_class])
+    return ranges
+
+
+def decompositions():
+    """Returns the canonical and compatibility decompositions, each one level deep."""
+    canonical = []
+    compatibility = []
+    for c in characters():
+        fields = unicodedata.decomposition(c).split()
+        if not fields:
+            continue
+        if fields[0].startswith("<"):
+            compatibility.append((c, [chr(int(f, 16)) for f in fields[1:]]))
+        else:
+            canonical.append((c, [chr(int(f, 16)) for f in fields]))
+    return canonical, compatibility
+
+
+def compositions(canonical):
+    """Pairs composing into a primary composite, i.e. not excluded from composition."""
+    pairs = []
+    for c, decomposition in canonical:
+        if len(decomposition) == 2 and unicodedata.normalize("NFC", c) == c:
+            pairs.append((decomposition[0], decomposition[1], c))
+    return sorted(pairs)
+
+
+def main():
+    canonical, compatibility = decompositions()
+    lines = [HEADER.format(version=unicodedata.unidata_version)]
+    lines.append(
+        "/// Canonical combining classes of all characters whose class isn't 0, as inclusive ranges."
+    )
+    lines.append("pub const COMBINING_CLASSES: &[(char, char, u8)] = &[")
+    for first, last, combining_class in combining_class_ranges():
+        lines.append("    (%s, %s, %d)," % (literal(first), literal(last), combining_class))
+    lines.append("];\n")
+    for doc, name, table in [
+        ("Single-level canonical decompositions", "CANONICAL_DECOMPOSITIONS", canonical),
+        ("Single-level compatibility decompositions", "COMPATIBILITY_DECOMPOSITIONS", compatibility),
+    ]:
+        lines.append("/// %s, sorted by character." % doc)
+        lines.append("pub const %s: &[(char, &[char])] = &[" % name)
+        for c, decomposition in table:
+            lines.append(
+                "    (%s, &[%s]),"
+                % (literal(c), ", ".join(literal(d) for d in decomposition))
+            )
+        lines.append("];\n")
+    lines.append(
+        "/// Pairs of characters that canonically compose into a primary composite, sorted by pair."
+    )
+    lines.append("pub const COMPOSITIONS: &[(char, char, char)] = &[")
+    for first, second, composite in compositions(canonical):
+        lines.append("    (%s, %s, %s)," % (literal(first), literal(second), literal(composite)))
+    lines.append("];")
+    print("\n".join(lines))
+
+
+if __name__ == "__main__":
+    main()